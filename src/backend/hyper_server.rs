@@ -0,0 +1,137 @@
+use std::convert::Infallible;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::TryStreamExt;
+use http::Request;
+use http_body_util::{BodyDataStream, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::service::service_fn;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use crate::net::{Body, HttpHandler, ServerHandle};
+use crate::{Backend, Capabilities, Capability, ExtioError, HttpServer};
+
+/// [`HttpServer`] backend built on `hyper`, serving HTTP/1.1 and HTTP/2
+/// over plain TCP.
+///
+/// Request and response bodies are streamed. Shutting the server down stops
+/// accepting connections; connections already open are served to
+/// completion.
+///
+/// Failed accepts are logged through the `log` crate. When the process runs
+/// out of file descriptors or memory the server pauses briefly before
+/// accepting again; if the listener itself breaks, the server stops.
+#[derive(Debug, Clone, Default)]
+pub struct HyperServer;
+
+impl HyperServer {
+    /// Creates a new hyper server backend.
+    pub fn new() -> Self {
+        HyperServer
+    }
+}
+
+impl Backend for HyperServer {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capability::HttpServer.into()
+    }
+}
+
+#[async_trait]
+impl HttpServer for HyperServer {
+    async fn http_serve(
+        &self,
+        addr: &str,
+        handler: Arc<dyn HttpHandler>,
+    ) -> Result<ServerHandle, Self::Error> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+
+        let task = tokio::spawn(async move {
+            loop {
+                let accepted = tokio::select! {
+                    _ = &mut shutdown_rx => break,
+                    accepted = listener.accept() => accepted,
+                };
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => match accept_backoff(&err) {
+                        Some(Duration::ZERO) => {
+                            log::debug!("accept on {local_addr} failed: {err}");
+                            continue;
+                        }
+                        Some(delay) => {
+                            log::warn!(
+                                "accept on {local_addr} failed, retrying in {delay:?}: {err}"
+                            );
+                            tokio::select! {
+                                _ = &mut shutdown_rx => break,
+                                _ = tokio::time::sleep(delay) => continue,
+                            }
+                        }
+                        None => {
+                            log::error!(
+                                "accept on {local_addr} failed, stopping the server: {err}"
+                            );
+                            break;
+                        }
+                    },
+                };
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |req: Request<Incoming>| {
+                        let handler = handler.clone();
+                        async move {
+                            let req = req.map(|body| Body::from_stream(BodyDataStream::new(body)));
+                            let resp = handler.handle(req).await;
+                            Ok::<_, Infallible>(
+                                resp.map(|body| StreamBody::new(body.map_ok(Frame::data))),
+                            )
+                        }
+                    });
+                    let _ = auto::Builder::new(TokioExecutor::new())
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        Ok(ServerHandle::new(local_addr.to_string(), shutdown_tx, task))
+    }
+}
+
+/// How long to wait before accepting again after `err`, or `None` if the
+/// listener is unusable.
+///
+/// Errors about a single connection are retried at once. Anything else is
+/// assumed to be resource exhaustion (`EMFILE`, `ENFILE`, `ENOBUFS`,
+/// `ENOMEM`), which persists for a while, so retrying straight away would
+/// spin.
+fn accept_backoff(err: &io::Error) -> Option<Duration> {
+    match err.kind() {
+        io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionRefused
+        | io::ErrorKind::Interrupted
+        | io::ErrorKind::WouldBlock => Some(Duration::ZERO),
+        io::ErrorKind::InvalidInput | io::ErrorKind::NotConnected => None,
+        #[cfg(unix)]
+        _ if matches!(
+            err.raw_os_error(),
+            Some(libc::EBADF | libc::ENOTSOCK | libc::EOPNOTSUPP)
+        ) =>
+        {
+            None
+        }
+        _ => Some(Duration::from_millis(100)),
+    }
+}
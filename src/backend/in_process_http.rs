@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use async_trait::async_trait;
+use http::{Request, Response};
+use tokio::sync::oneshot;
+
+use crate::net::{Body, HttpHandler, ServerHandle};
+use crate::{Backend, Capabilities, Capability, ExtioError, HttpClient, HttpServer};
+
+type Routes = Arc<Mutex<HashMap<String, Arc<dyn HttpHandler>>>>;
+
+/// In-process HTTP transport for tests.
+///
+/// `http_serve` registers the handler under `addr` instead of opening a
+/// socket, and `http_request` dispatches any request whose URI authority
+/// equals a registered `addr` straight to its handler. Clones share the
+/// same registry, so a server and its clients can be handed different
+/// clones. Requests to an unregistered authority fail with
+/// [`ExtioError::ConnectionRefused`].
+#[derive(Clone, Default)]
+pub struct InProcessHttp {
+    routes: Routes,
+}
+
+impl InProcessHttp {
+    /// Creates a transport with no servers registered.
+    pub fn new() -> Self {
+        InProcessHttp::default()
+    }
+
+    fn routes(&self) -> MutexGuard<'_, HashMap<String, Arc<dyn HttpHandler>>> {
+        self.routes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for InProcessHttp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InProcessHttp")
+            .field("addrs", &self.routes().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Backend for InProcessHttp {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::from([Capability::Http, Capability::HttpServer])
+    }
+}
+
+#[async_trait]
+impl HttpServer for InProcessHttp {
+    async fn http_serve(
+        &self,
+        addr: &str,
+        handler: Arc<dyn HttpHandler>,
+    ) -> Result<ServerHandle, Self::Error> {
+        {
+            let mut routes = self.routes();
+            if routes.contains_key(addr) {
+                return Err(ExtioError::AlreadyExists(format!(
+                    "{addr} is already being served"
+                )));
+            }
+            routes.insert(addr.to_owned(), handler);
+        }
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            let _ = shutdown_rx.await;
+        });
+        // Unregister as soon as the handle goes, so the address can be
+        // served again straight away.
+        let routes = self.routes.clone();
+        let key = addr.to_owned();
+        Ok(
+            ServerHandle::new(addr, shutdown_tx, task).on_close(move || {
+                routes
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .remove(&key);
+            }),
+        )
+    }
+}
+
+#[async_trait]
+impl HttpClient for InProcessHttp {
+    async fn http_request(&self, req: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, Self::Error> {
+        let resp = self.http_request_stream(req.map(Body::from)).await?;
+        let (parts, body) = resp.into_parts();
+        let body = body.collect().await.map_err(ExtioError::Backend)?;
+        Ok(Response::from_parts(parts, body.to_vec()))
+    }
+
+    async fn http_request_stream(&self, req: Request<Body>) -> Result<Response<Body>, Self::Error> {
+        let authority = req
+            .uri()
+            .authority()
+            .ok_or_else(|| ExtioError::InvalidInput(format!("{} has no authority", req.uri())))?
+            .as_str();
+        let handler = self.routes().get(authority).cloned().ok_or_else(|| {
+            ExtioError::ConnectionRefused(format!("nothing is serving {authority}"))
+        })?;
+        Ok(handler.handle(req).await)
+    }
+}
@@ -0,0 +1,787 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tokio::sync::{Notify, mpsc, oneshot};
+
+use crate::mq::{Acknowledger, Delivery, Message};
+use crate::{Backend, Capabilities, Capability, ExtioError, MessageQueue};
+
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// First bytes of a broker log file; bumped if the record format changes.
+const MAGIC: &[u8; 8] = b"EXTIOMQ3";
+
+/// Record header: body length, body checksum and a checksum of those two,
+/// all little-endian `u32`s.
+const HEADER_LEN: usize = 12;
+
+const PUBLISH: u8 = 1;
+const JOIN: u8 = 2;
+const SETTLE: u8 = 3;
+
+/// [`MessageQueue`] backend for a single process: an in-memory broker,
+/// optionally persisted to an append-only log file.
+///
+/// Every consumer group of a topic receives every message, and the workers
+/// of a group share them. Each group keeps its own offset into the topic,
+/// so a slow group does not hold up the others; a group consuming a topic
+/// for the first time starts at the oldest message still buffered. Message
+/// ids are offsets, unique within their topic.
+///
+/// Each topic buffers at most `capacity` messages, and a message leaves
+/// the buffer once every group has settled it. Publishing to a full topic
+/// waits for room, so a topic nobody consumes yet holds `capacity`
+/// messages for its first group before publishers block.
+///
+/// With [`open`](Self::open), publishes, new groups and settlements are
+/// appended to the log before the call returns, and replayed on the next
+/// open, so messages and group offsets survive a restart. The file is
+/// written by a dedicated thread, never under the broker's lock, and
+/// records queued together are written together.
+///
+/// If a write fails, the partial record is cut off again and the log stops
+/// accepting writes: the failed call and every later one that needs the log
+/// report an error until the broker is reopened. The failed call's change
+/// may already be visible in memory, but not after reopening. Deliveries that
+/// were not settled are redelivered afterwards, but redelivery counts start
+/// over. The log is compacted to the live state on open. Records are not
+/// `fsync`ed, so they survive a crash of the process but not of the
+/// machine.
+///
+/// Cloning a `LocalBroker` shares the broker.
+#[derive(Debug, Clone)]
+pub struct LocalBroker {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    /// Notified on every publish and settlement.
+    changed: Notify,
+}
+
+impl LocalBroker {
+    /// Creates an in-memory broker with the default capacity of 1024
+    /// messages per topic.
+    pub fn new() -> Self {
+        LocalBroker::builder().build()
+    }
+
+    /// Opens (creating if needed) the broker log at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ExtioError> {
+        LocalBroker::builder().open(path)
+    }
+
+    /// Returns a builder for configuring the broker.
+    pub fn builder() -> LocalBrokerBuilder {
+        LocalBrokerBuilder::default()
+    }
+
+    /// Number of messages of `topic` still buffered.
+    pub fn buffered(&self, topic: &str) -> usize {
+        self.state()
+            .topics
+            .get(topic)
+            .map_or(0, |topic| topic.messages.len())
+    }
+
+    fn from_state(state: State) -> Self {
+        LocalBroker {
+            shared: Arc::new(Shared {
+                state: Mutex::new(state),
+                changed: Notify::new(),
+            }),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.shared)
+    }
+
+    /// Waits until `f` returns a value, re-checking after every publish or
+    /// settlement.
+    async fn wait_for<T, F>(&self, mut f: F) -> io::Result<T>
+    where
+        F: FnMut(&mut State) -> io::Result<Option<T>>,
+    {
+        loop {
+            let changed = self.shared.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+            if let Some(value) = f(&mut self.state())? {
+                return Ok(value);
+            }
+            changed.await;
+        }
+    }
+}
+
+impl Default for LocalBroker {
+    fn default() -> Self {
+        LocalBroker::new()
+    }
+}
+
+/// Builder for [`LocalBroker`].
+#[derive(Debug)]
+pub struct LocalBrokerBuilder {
+    capacity: usize,
+}
+
+impl Default for LocalBrokerBuilder {
+    fn default() -> Self {
+        LocalBrokerBuilder {
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+}
+
+impl LocalBrokerBuilder {
+    /// Maximum number of messages buffered per topic, at least 1 (default
+    /// 1024).
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Creates an in-memory broker.
+    pub fn build(self) -> LocalBroker {
+        LocalBroker::from_state(State::new(self.capacity))
+    }
+
+    /// Opens (creating if needed) the broker log at `path`, replaying and
+    /// compacting it.
+    ///
+    /// A damaged record at the end of the log, as left by a crash while
+    /// writing, is discarded; damage anywhere else is an error.
+    pub fn open(self, path: impl AsRef<Path>) -> Result<LocalBroker, ExtioError> {
+        let path = path.as_ref();
+        let mut state = State::new(self.capacity);
+        match fs::read(path) {
+            Ok(data) => state.replay(&data)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        state.log = Some(LogWriter::spawn(state.compact(path)?)?);
+        Ok(LocalBroker::from_state(state))
+    }
+}
+
+/// A message taken for a consumer by [`State::consume`].
+struct Taken {
+    offset: u64,
+    redeliveries: u32,
+    message: Message,
+    timestamp: SystemTime,
+    written: Written,
+}
+
+#[derive(Debug)]
+struct State {
+    capacity: usize,
+    topics: HashMap<String, Topic>,
+    log: Option<LogWriter>,
+}
+
+/// Buffered messages of a topic, and where each group is in it.
+#[derive(Debug, Default)]
+struct Topic {
+    /// Offset of the first buffered message.
+    base: u64,
+    messages: VecDeque<(Message, SystemTime)>,
+    groups: HashMap<String, Group>,
+}
+
+#[derive(Debug, Default)]
+struct Group {
+    /// Offset of the next message not yet delivered to the group.
+    next: u64,
+    /// Requeued offsets, delivered before new ones.
+    retry: VecDeque<u64>,
+    /// Delivered offsets not settled yet, with their redelivery counts.
+    unsettled: BTreeMap<u64, u32>,
+    /// Settled offsets at or after `next`; only a replayed log has them.
+    settled: BTreeSet<u64>,
+}
+
+impl Group {
+    /// Offset of the oldest message the group still needs.
+    fn floor(&self) -> u64 {
+        self.unsettled
+            .keys()
+            .next()
+            .map_or(self.next, |&offset| offset.min(self.next))
+    }
+
+    fn skip_settled(&mut self) {
+        while self.settled.remove(&self.next) {
+            self.next += 1;
+        }
+    }
+}
+
+impl Topic {
+    /// Offset the next published message gets.
+    fn end(&self) -> u64 {
+        self.base + self.messages.len() as u64
+    }
+
+    fn get(&self, offset: u64) -> &(Message, SystemTime) {
+        &self.messages[(offset - self.base) as usize]
+    }
+
+    /// Picks the next offset for `group`, with its redelivery count.
+    fn next(&mut self, group: &str) -> Option<(u64, u32)> {
+        let end = self.end();
+        let group = self.groups.get_mut(group)?;
+        if let Some(offset) = group.retry.pop_front() {
+            return Some((offset, group.unsettled[&offset]));
+        }
+        if group.next < end {
+            let offset = group.next;
+            group.next += 1;
+            group.skip_settled();
+            group.unsettled.insert(offset, 0);
+            return Some((offset, 0));
+        }
+        None
+    }
+
+    /// Drops the messages every group has settled.
+    fn trim(&mut self) {
+        let Some(floor) = self.groups.values().map(Group::floor).min() else {
+            return;
+        };
+        while self.base < floor && self.messages.pop_front().is_some() {
+            self.base += 1;
+        }
+    }
+}
+
+impl State {
+    fn new(capacity: usize) -> Self {
+        State {
+            capacity,
+            topics: HashMap::new(),
+            log: None,
+        }
+    }
+
+    /// Appends `message` to `topic` if it has room.
+    ///
+    /// `message` is taken only once it is published.
+    fn publish(
+        &mut self,
+        topic: &str,
+        message: &mut Option<Message>,
+        timestamp: SystemTime,
+    ) -> io::Result<Option<(u64, Written)>> {
+        let State {
+            capacity,
+            topics,
+            log,
+        } = self;
+        check_log(log)?;
+        let entry = topics.entry(topic.to_owned()).or_default();
+        if entry.messages.len() >= *capacity {
+            return Ok(None);
+        }
+        let message = message.take().expect("message published twice");
+        let offset = entry.end();
+        let written = append(log, |buf| {
+            encode_publish(buf, topic, offset, &message, timestamp)
+        });
+        entry.messages.push_back((message, timestamp));
+        Ok(Some((offset, written)))
+    }
+
+    /// Takes the next message of `topic` for `group`, creating the group at
+    /// the oldest buffered message if needed.
+    fn consume(&mut self, topic: &str, group: &str) -> io::Result<Option<Taken>> {
+        let State { topics, log, .. } = self;
+        let entry = topics.entry(topic.to_owned()).or_default();
+        let mut written = None;
+        if !entry.groups.contains_key(group) {
+            check_log(log)?;
+            let offset = entry.base;
+            written = append(log, |buf| encode_group(buf, JOIN, topic, group, offset));
+            let joined = Group {
+                next: offset,
+                ..Group::default()
+            };
+            entry.groups.insert(group.to_owned(), joined);
+        }
+        let Some((offset, redeliveries)) = entry.next(group) else {
+            // The join, if any, is written anyway; nobody waits for it.
+            return Ok(None);
+        };
+        let (message, timestamp) = entry.get(offset).clone();
+        Ok(Some(Taken {
+            offset,
+            redeliveries,
+            message,
+            timestamp,
+            written,
+        }))
+    }
+
+    /// Settles a delivery, or returns it to its group.
+    fn settle(
+        &mut self,
+        topic: &str,
+        group: &str,
+        offset: u64,
+        requeue: bool,
+    ) -> io::Result<Written> {
+        let State { topics, log, .. } = self;
+        let Some(entry) = topics.get_mut(topic) else {
+            return Ok(None);
+        };
+        let Some(state) = entry.groups.get_mut(group) else {
+            return Ok(None);
+        };
+        let Some(redeliveries) = state.unsettled.get_mut(&offset) else {
+            return Ok(None);
+        };
+        if requeue {
+            *redeliveries += 1;
+            state.retry.push_back(offset);
+            return Ok(None);
+        }
+        check_log(log)?;
+        let written = append(log, |buf| encode_group(buf, SETTLE, topic, group, offset));
+        state.unsettled.remove(&offset);
+        entry.trim();
+        Ok(written)
+    }
+
+    /// Rebuilds the broker from the records of a log file.
+    fn replay(&mut self, data: &[u8]) -> io::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let mut rest = data.strip_prefix(MAGIC).ok_or_else(corrupt)?;
+        while let Some((header, body)) = rest.split_first_chunk::<HEADER_LEN>() {
+            let [len, sum, check] = [0, 4, 8]
+                .map(|i| u32::from_le_bytes(header[i..i + 4].try_into().expect("4 bytes")));
+            if checksum(&header[..8]) != check {
+                // A torn header is shorter than `HEADER_LEN`, so this one is
+                // garbled and its length cannot be trusted to find the next.
+                return Err(corrupt());
+            }
+            let Some(record) = body.get(..len as usize) else {
+                // Cut short by a crash; everything before it is intact.
+                break;
+            };
+            rest = &body[record.len()..];
+            if checksum(record) != sum || self.apply(record).is_none() {
+                if rest.is_empty() {
+                    // Torn by a crash or a failed write, as above.
+                    break;
+                }
+                return Err(corrupt());
+            }
+        }
+        for topic in self.topics.values_mut() {
+            topic.groups.values_mut().for_each(Group::skip_settled);
+            topic.trim();
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, record: &[u8]) -> Option<()> {
+        let mut r = Reader(record);
+        let tag = r.u8()?;
+        let topic = self.topics.entry(r.str()?.to_owned()).or_default();
+        match tag {
+            PUBLISH => {
+                let offset = r.u64()?;
+                let timestamp = UNIX_EPOCH
+                    .checked_add(Duration::from_secs(r.u64()?))?
+                    .checked_add(Duration::from_nanos(r.u32()?.into()))?;
+                let mut message = Message::default();
+                for _ in 0..r.u32()? {
+                    message
+                        .headers
+                        .insert(r.str()?.to_owned(), r.str()?.to_owned());
+                }
+                message.payload = r.bytes()?.to_vec();
+                if topic.messages.is_empty() {
+                    topic.base = offset;
+                } else if offset != topic.end() {
+                    return None;
+                }
+                topic.messages.push_back((message, timestamp));
+            }
+            JOIN => {
+                let group = r.str()?.to_owned();
+                let next = r.u64()?;
+                if topic.messages.is_empty() {
+                    topic.base = next;
+                }
+                topic.groups.insert(
+                    group,
+                    Group {
+                        next,
+                        ..Group::default()
+                    },
+                );
+            }
+            SETTLE => {
+                let group = topic.groups.get_mut(r.str()?)?;
+                group.settled.insert(r.u64()?);
+            }
+            _ => return None,
+        }
+        r.0.is_empty().then_some(())
+    }
+
+    /// Rewrites the log at `path` with just the current state and opens it
+    /// for appending.
+    ///
+    /// Only called right after replaying, when no delivery is in flight.
+    fn compact(&self, path: &Path) -> io::Result<File> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        let mut out = BufWriter::new(File::create(&tmp)?);
+        out.write_all(MAGIC)?;
+        let mut buf = Vec::new();
+        for (name, topic) in &self.topics {
+            for (i, (message, timestamp)) in topic.messages.iter().enumerate() {
+                record(&mut buf, |buf| {
+                    encode_publish(buf, name, topic.base + i as u64, message, *timestamp)
+                });
+                out.write_all(&buf)?;
+            }
+            for (group_name, group) in &topic.groups {
+                record(&mut buf, |buf| {
+                    encode_group(buf, JOIN, name, group_name, group.next)
+                });
+                out.write_all(&buf)?;
+                for &offset in &group.settled {
+                    record(&mut buf, |buf| {
+                        encode_group(buf, SETTLE, name, group_name, offset)
+                    });
+                    out.write_all(&buf)?;
+                }
+            }
+        }
+        out.into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()?;
+        fs::rename(&tmp, path)?;
+        OpenOptions::new().append(true).open(path)
+    }
+}
+
+impl Backend for LocalBroker {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capability::Mq.into()
+    }
+}
+
+#[async_trait]
+impl MessageQueue for LocalBroker {
+    /// Waits while the topic is full.
+    async fn mq_publish_with(&self, topic: &str, message: Message) -> Result<String, Self::Error> {
+        let timestamp = SystemTime::now();
+        let mut message = Some(message);
+        let (offset, written) = self
+            .wait_for(|state| state.publish(topic, &mut message, timestamp))
+            .await?;
+        self.shared.changed.notify_waiters();
+        flushed(written).await?;
+        Ok(offset.to_string())
+    }
+
+    async fn mq_consume_group(
+        &self,
+        topic: &str,
+        group: &str,
+    ) -> Result<Delivery<Self::Error>, Self::Error> {
+        let taken = self.wait_for(|state| state.consume(topic, group)).await?;
+        let acker = BrokerAcker {
+            shared: self.shared.clone(),
+            topic: topic.to_owned(),
+            group: group.to_owned(),
+            offset: taken.offset,
+            settled: false,
+        };
+        // The delivery requeues itself if the write fails.
+        let delivery = Delivery::new(
+            taken.offset.to_string(),
+            taken.message,
+            taken.timestamp,
+            acker,
+        )
+        .with_redeliveries(taken.redeliveries);
+        flushed(taken.written).await?;
+        Ok(delivery)
+    }
+}
+
+/// Settles a broker delivery; requeues it if dropped unsettled.
+struct BrokerAcker {
+    shared: Arc<Shared>,
+    topic: String,
+    group: String,
+    offset: u64,
+    settled: bool,
+}
+
+impl BrokerAcker {
+    fn settle(&mut self, requeue: bool) -> io::Result<Written> {
+        let written = lock(&self.shared).settle(&self.topic, &self.group, self.offset, requeue)?;
+        self.settled = true;
+        self.shared.changed.notify_waiters();
+        Ok(written)
+    }
+}
+
+impl Drop for BrokerAcker {
+    fn drop(&mut self) {
+        if !self.settled {
+            // Requeueing is not logged, so it cannot fail.
+            let _ = self.settle(true);
+        }
+    }
+}
+
+#[async_trait]
+impl Acknowledger for BrokerAcker {
+    type Error = ExtioError;
+
+    async fn ack(mut self: Box<Self>) -> Result<(), Self::Error> {
+        let written = self.settle(false)?;
+        Ok(flushed(written).await?)
+    }
+
+    async fn nack(mut self: Box<Self>) -> Result<(), Self::Error> {
+        let written = self.settle(false)?;
+        Ok(flushed(written).await?)
+    }
+
+    async fn requeue(mut self: Box<Self>) -> Result<(), Self::Error> {
+        self.settle(true)?;
+        Ok(())
+    }
+}
+
+fn lock(shared: &Shared) -> MutexGuard<'_, State> {
+    shared.state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Resolves once a log record is written; `None` when nothing was logged.
+type Written = Option<oneshot::Receiver<io::Result<()>>>;
+
+/// Appends log records on a dedicated thread.
+#[derive(Debug)]
+struct LogWriter {
+    queue: mpsc::UnboundedSender<Append>,
+    /// Set by the thread once a write has failed.
+    failed: Arc<AtomicBool>,
+}
+
+/// A record waiting to be written, and who to tell once it is.
+#[derive(Debug)]
+struct Append {
+    record: Vec<u8>,
+    done: oneshot::Sender<io::Result<()>>,
+}
+
+impl LogWriter {
+    /// Starts the writer thread; it stops once the writer is dropped.
+    fn spawn(file: File) -> io::Result<Self> {
+        let (queue, appends) = mpsc::unbounded_channel();
+        let failed = Arc::new(AtomicBool::new(false));
+        let len = file.metadata()?.len();
+        let flag = failed.clone();
+        std::thread::Builder::new()
+            .name("extio-broker-log".to_owned())
+            .spawn(move || write_log(file, len, appends, &flag))?;
+        Ok(LogWriter { queue, failed })
+    }
+
+    /// Queues a record; records are written in the order they are queued.
+    fn append(&self, encode: impl FnOnce(&mut Vec<u8>)) -> oneshot::Receiver<io::Result<()>> {
+        let mut buf = Vec::new();
+        record(&mut buf, encode);
+        let (done, written) = oneshot::channel();
+        // Only fails if the thread died, which `flushed` reports.
+        let _ = self.queue.send(Append { record: buf, done });
+        written
+    }
+}
+
+/// Writer thread: writes whatever is queued in one go, then reports back.
+///
+/// After a failed write the file is truncated back to `len`, its length
+/// before the write, so no torn record is left for later ones to follow,
+/// and nothing more is written.
+fn write_log(
+    mut file: File,
+    mut len: u64,
+    mut appends: mpsc::UnboundedReceiver<Append>,
+    failed: &AtomicBool,
+) {
+    let mut batch = Vec::new();
+    let mut buf = Vec::new();
+    while let Some(append) = appends.blocking_recv() {
+        batch.push(append);
+        while let Ok(append) = appends.try_recv() {
+            batch.push(append);
+        }
+        buf.clear();
+        for append in &batch {
+            buf.extend_from_slice(&append.record);
+        }
+        let result = if failed.load(Ordering::Acquire) {
+            Err(log_failed())
+        } else {
+            file.write_all(&buf)
+        };
+        match &result {
+            Ok(()) => len += buf.len() as u64,
+            Err(_) if !failed.swap(true, Ordering::AcqRel) => {
+                let _ = file.set_len(len);
+            }
+            Err(_) => {}
+        }
+        for append in batch.drain(..) {
+            let result = match &result {
+                Ok(()) => Ok(()),
+                Err(err) => Err(io::Error::new(err.kind(), err.to_string())),
+            };
+            let _ = append.done.send(result);
+        }
+    }
+}
+
+/// Fails if the broker's log can no longer be written.
+fn check_log(log: &Option<LogWriter>) -> io::Result<()> {
+    match log {
+        Some(log) if log.failed.load(Ordering::Acquire) => Err(log_failed()),
+        _ => Ok(()),
+    }
+}
+
+fn log_failed() -> io::Error {
+    io::Error::other("message broker log failed earlier; reopen the broker")
+}
+
+/// Queues a record, if the broker has a log.
+fn append(log: &Option<LogWriter>, encode: impl FnOnce(&mut Vec<u8>)) -> Written {
+    log.as_ref().map(|log| log.append(encode))
+}
+
+/// Waits until a record queued with [`append`] is written.
+async fn flushed(written: Written) -> io::Result<()> {
+    match written {
+        Some(written) => written
+            .await
+            .unwrap_or_else(|_| Err(io::Error::other("message broker log writer stopped"))),
+        None => Ok(()),
+    }
+}
+
+/// Replaces `buf` with a record: header, then what `encode` writes.
+fn record(buf: &mut Vec<u8>, encode: impl FnOnce(&mut Vec<u8>)) {
+    buf.clear();
+    buf.extend_from_slice(&[0; HEADER_LEN]);
+    encode(buf);
+    let len = u32::try_from(buf.len() - HEADER_LEN).expect("record over 4 GiB");
+    let sum = checksum(&buf[HEADER_LEN..]);
+    buf[..4].copy_from_slice(&len.to_le_bytes());
+    buf[4..8].copy_from_slice(&sum.to_le_bytes());
+    let check = checksum(&buf[..8]);
+    buf[8..HEADER_LEN].copy_from_slice(&check.to_le_bytes());
+}
+
+/// 32-bit FNV-1a; catches torn and garbled records, not tampering.
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &b| {
+        (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+    })
+}
+
+fn encode_publish(
+    buf: &mut Vec<u8>,
+    topic: &str,
+    offset: u64,
+    message: &Message,
+    timestamp: SystemTime,
+) {
+    let since_epoch = timestamp.duration_since(UNIX_EPOCH).unwrap_or_default();
+    buf.push(PUBLISH);
+    put_bytes(buf, topic.as_bytes());
+    buf.extend_from_slice(&offset.to_le_bytes());
+    buf.extend_from_slice(&since_epoch.as_secs().to_le_bytes());
+    buf.extend_from_slice(&since_epoch.subsec_nanos().to_le_bytes());
+    buf.extend_from_slice(&(message.headers.len() as u32).to_le_bytes());
+    for (key, value) in &message.headers {
+        put_bytes(buf, key.as_bytes());
+        put_bytes(buf, value.as_bytes());
+    }
+    put_bytes(buf, &message.payload);
+}
+
+fn encode_group(buf: &mut Vec<u8>, tag: u8, topic: &str, group: &str, offset: u64) {
+    buf.push(tag);
+    put_bytes(buf, topic.as_bytes());
+    put_bytes(buf, group.as_bytes());
+    buf.extend_from_slice(&offset.to_le_bytes());
+}
+
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+/// Reads the fields of a log record.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.0.split_first_chunk::<N>()?;
+        self.0 = rest;
+        Some(*head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[b]| b)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        let bytes = self.0.get(..len)?;
+        self.0 = &self.0[len..];
+        Some(bytes)
+    }
+
+    fn str(&mut self) -> Option<&'a str> {
+        std::str::from_utf8(self.bytes()?).ok()
+    }
+}
+
+fn corrupt() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "corrupt message broker log")
+}
@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+use crate::fs::{DirEntry, Metadata, Permissions, WriteOptions};
+use crate::{Backend, ByteReader, Capabilities, Capability, ExtioError, FileIo};
+
+/// [`FileIo`] backend for the local filesystem, built on `tokio::fs`.
+///
+/// Paths are used as given, so relative paths resolve against the process
+/// working directory. I/O failures are reported as [`ExtioError`] with the
+/// `std::io::ErrorKind` mapped onto the matching variant.
+#[derive(Debug, Clone, Default)]
+pub struct LocalFs;
+
+impl LocalFs {
+    /// Creates a new local filesystem backend.
+    pub fn new() -> Self {
+        LocalFs
+    }
+}
+
+impl Backend for LocalFs {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capability::Files.into()
+    }
+}
+
+#[async_trait]
+impl FileIo for LocalFs {
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>, Self::Error> {
+        Ok(fs::read(path).await?)
+    }
+
+    async fn write_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
+        Ok(fs::write(path, data).await?)
+    }
+
+    async fn delete_file(&self, path: &Path) -> Result<(), Self::Error> {
+        Ok(fs::remove_file(path).await?)
+    }
+
+    async fn write_file_with(
+        &self,
+        path: &Path,
+        data: &[u8],
+        options: WriteOptions,
+    ) -> Result<(), Self::Error> {
+        if options.atomic {
+            return atomic_write(path, data, options.create_new).await;
+        }
+        if !options.create_new {
+            return self.write_file(path, data).await;
+        }
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .await?;
+        file.write_all(data).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn append_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        file.write_all(data).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn stat(&self, path: &Path) -> Result<Metadata, Self::Error> {
+        Ok(fs::metadata(path).await?.into())
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, Self::Error> {
+        Ok(fs::try_exists(path).await?)
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<(), Self::Error> {
+        Ok(fs::rename(from, to).await?)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<u64, Self::Error> {
+        Ok(fs::copy(from, to).await?)
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<(), Self::Error> {
+        Ok(fs::create_dir_all(path).await?)
+    }
+
+    async fn remove_dir_all(&self, path: &Path) -> Result<(), Self::Error> {
+        Ok(fs::remove_dir_all(path).await?)
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &Path,
+        permissions: Permissions,
+    ) -> Result<(), Self::Error> {
+        let mut perms = fs::metadata(path).await?.permissions();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = match permissions.mode {
+                Some(mode) => mode,
+                None if permissions.readonly => perms.mode() & !0o222,
+                // `set_readonly(false)` would make it writable by everyone.
+                None => perms.mode() | 0o200,
+            };
+            perms.set_mode(mode);
+        }
+        #[cfg(not(unix))]
+        perms.set_readonly(permissions.readonly);
+        Ok(fs::set_permissions(path, perms).await?)
+    }
+
+    async fn list_dir(&self, path: &Path) -> Result<Vec<DirEntry>, Self::Error> {
+        let mut list = Vec::new();
+        let mut entries = fs::read_dir(path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let meta = entry.metadata().await?;
+            list.push(DirEntry::new(entry.path(), meta.into()));
+        }
+        list.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(list)
+    }
+
+    async fn read_file_stream(&self, path: &Path) -> Result<ByteReader, Self::Error> {
+        Ok(Box::pin(fs::File::open(path).await?))
+    }
+
+    async fn write_file_stream(
+        &self,
+        path: &Path,
+        mut reader: ByteReader,
+    ) -> Result<u64, Self::Error> {
+        let mut file = fs::File::create(path).await?;
+        let written = tokio::io::copy(&mut reader, &mut file).await?;
+        file.flush().await?;
+        Ok(written)
+    }
+}
+
+/// Writes `data` to a temporary sibling of `path`, fsyncs it and moves it
+/// into place, then fsyncs the parent directory so the rename is durable.
+///
+/// With `create_new` the temporary file is hard-linked instead of renamed,
+/// which fails if `path` already exists.
+async fn atomic_write(path: &Path, data: &[u8], create_new: bool) -> Result<(), ExtioError> {
+    let tmp = temp_sibling(path)?;
+    let result = async {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp)
+            .await?;
+        file.write_all(data).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        if create_new {
+            fs::hard_link(&tmp, path).await?;
+            fs::remove_file(&tmp).await
+        } else {
+            fs::rename(&tmp, path).await
+        }
+    }
+    .await;
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp).await;
+    }
+    result?;
+
+    sync_parent(path).await
+}
+
+fn temp_sibling(path: &Path) -> Result<PathBuf, ExtioError> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let name = path
+        .file_name()
+        .ok_or_else(|| ExtioError::InvalidInput(format!("{} has no file name", path.display())))?;
+    let tmp = format!(
+        ".{}.{}.{}.tmp",
+        name.to_string_lossy(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    Ok(path.with_file_name(tmp))
+}
+
+#[cfg(unix)]
+async fn sync_parent(path: &Path) -> Result<(), ExtioError> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::File::open(parent).await?.sync_all().await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn sync_parent(_path: &Path) -> Result<(), ExtioError> {
+    // std cannot open a directory for fsync on this platform; the file data
+    // itself was synced before the rename.
+    Ok(())
+}
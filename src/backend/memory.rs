@@ -0,0 +1,692 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+
+use crate::fs::{DirEntry, FileKind, Metadata, Permissions, WriteOptions};
+use crate::mq::{Acknowledger, Delivery, Message};
+use crate::{
+    Backend, Capabilities, Capability, Clock, Crypto, Database, Env, Exec, ExtioError, FileIo,
+    HttpClient, HttpServer, Ipc, Logger, MessageQueue, Metrics, ObjectStore, Tcp, Udp, WebSocket,
+};
+
+/// A log entry captured by [`InMemoryExtio`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// Severity level as passed to [`Logger::log`].
+    pub level: String,
+    /// Log message.
+    pub message: String,
+}
+
+/// In-memory backend for tests.
+///
+/// Implements files (a virtual directory tree), object storage, message
+/// queue topics, IPC channels, environment variables, logging and metrics
+/// entirely in memory, and exposes inspection methods so tests can assert
+/// what was written, published and logged. Every other capability trait is
+/// implemented with its defaults, so the type satisfies [`Extio`] and
+/// reports them as [`Unsupported`](crate::Unsupported).
+///
+/// `mq_consume` and `ipc_receive` wait until a message is available. Every
+/// published message is retained, so a new consumer group starts at the
+/// first message of the topic; unsettled deliveries are requeued.
+///
+/// [`Extio`]: crate::Extio
+#[derive(Debug, Default)]
+pub struct InMemoryExtio {
+    state: Arc<Mutex<State>>,
+    delivered: Arc<Notify>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    files: BTreeMap<PathBuf, Node>,
+    objects: BTreeMap<String, Vec<u8>>,
+    topics: HashMap<String, Topic>,
+    channels: HashMap<String, VecDeque<Vec<u8>>>,
+    sent: HashMap<String, Vec<Vec<u8>>>,
+    env: BTreeMap<String, String>,
+    logs: Vec<LogRecord>,
+    metrics: Vec<(String, f64)>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    data: Option<Vec<u8>>,
+    modified: SystemTime,
+    permissions: Permissions,
+}
+
+/// Every message published to a topic, and where each group is in it.
+#[derive(Debug, Default)]
+struct Topic {
+    messages: Vec<(Message, SystemTime)>,
+    groups: HashMap<String, Group>,
+}
+
+#[derive(Debug, Default)]
+struct Group {
+    /// Index of the next message not yet delivered to the group.
+    next: usize,
+    /// Requeued messages, delivered before new ones.
+    retry: VecDeque<usize>,
+    redeliveries: HashMap<usize, u32>,
+}
+
+impl Topic {
+    /// Picks the next message for `group`, with its redelivery count.
+    fn next(&mut self, group: &str) -> Option<(usize, u32)> {
+        let group = self.groups.entry(group.to_owned()).or_default();
+        if let Some(index) = group.retry.pop_front() {
+            return Some((index, group.redeliveries.get(&index).copied().unwrap_or(0)));
+        }
+        if group.next < self.messages.len() {
+            group.next += 1;
+            return Some((group.next - 1, 0));
+        }
+        None
+    }
+}
+
+/// Settles an in-memory delivery; requeues it if dropped unsettled.
+struct MemoryAcker {
+    state: Arc<Mutex<State>>,
+    delivered: Arc<Notify>,
+    topic: String,
+    group: String,
+    index: usize,
+    settled: bool,
+}
+
+impl MemoryAcker {
+    fn settle(&mut self, requeue: bool) {
+        self.settled = true;
+        let mut state = lock(&self.state);
+        let Some(group) = state
+            .topics
+            .get_mut(&self.topic)
+            .and_then(|topic| topic.groups.get_mut(&self.group))
+        else {
+            return;
+        };
+        if requeue {
+            *group.redeliveries.entry(self.index).or_default() += 1;
+            group.retry.push_back(self.index);
+            drop(state);
+            self.delivered.notify_waiters();
+        } else {
+            group.redeliveries.remove(&self.index);
+        }
+    }
+}
+
+impl Drop for MemoryAcker {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.settle(true);
+        }
+    }
+}
+
+#[async_trait]
+impl Acknowledger for MemoryAcker {
+    type Error = ExtioError;
+
+    async fn ack(mut self: Box<Self>) -> Result<(), Self::Error> {
+        self.settle(false);
+        Ok(())
+    }
+
+    async fn nack(mut self: Box<Self>) -> Result<(), Self::Error> {
+        self.settle(false);
+        Ok(())
+    }
+
+    async fn requeue(mut self: Box<Self>) -> Result<(), Self::Error> {
+        self.settle(true);
+        Ok(())
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Node {
+    fn file(data: Vec<u8>) -> Self {
+        Node {
+            data: Some(data),
+            modified: SystemTime::now(),
+            permissions: Permissions::default(),
+        }
+    }
+
+    fn dir() -> Self {
+        Node {
+            data: None,
+            modified: SystemTime::now(),
+            permissions: Permissions::default(),
+        }
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            kind: if self.data.is_some() {
+                FileKind::File
+            } else {
+                FileKind::Dir
+            },
+            size: self.data.as_ref().map_or(0, |data| data.len() as u64),
+            modified: Some(self.modified),
+            permissions: self.permissions,
+        }
+    }
+}
+
+impl InMemoryExtio {
+    /// Creates an empty backend.
+    pub fn new() -> Self {
+        InMemoryExtio::default()
+    }
+
+    /// Seeds a file, creating its parent directories.
+    pub fn with_file(self, path: impl Into<PathBuf>, data: impl Into<Vec<u8>>) -> Self {
+        {
+            let mut state = self.state();
+            let path = path.into();
+            for dir in path.ancestors().skip(1).filter(|dir| !is_root(dir)) {
+                state
+                    .files
+                    .entry(dir.to_path_buf())
+                    .or_insert_with(Node::dir);
+            }
+            state.files.insert(path, Node::file(data.into()));
+        }
+        self
+    }
+
+    /// Seeds an object in object storage.
+    pub fn with_object(self, key: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        self.state().objects.insert(key.into(), data.into());
+        self
+    }
+
+    /// Seeds an environment variable.
+    pub fn with_env(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.state().env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the contents of a file, or `None` if it does not exist or is
+    /// a directory.
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        self.state().files.get(path.as_ref())?.data.clone()
+    }
+
+    /// Returns every file (not directory) in the virtual tree.
+    pub fn files(&self) -> BTreeMap<PathBuf, Vec<u8>> {
+        let state = self.state();
+        state
+            .files
+            .iter()
+            .filter_map(|(path, node)| Some((path.clone(), node.data.clone()?)))
+            .collect()
+    }
+
+    /// Returns the object stored under `key`.
+    pub fn object(&self, key: &str) -> Option<Vec<u8>> {
+        self.state().objects.get(key).cloned()
+    }
+
+    /// Returns every object in object storage.
+    pub fn objects(&self) -> BTreeMap<String, Vec<u8>> {
+        self.state().objects.clone()
+    }
+
+    /// Returns every message published to `topic`, consumed or not, in
+    /// publish order.
+    pub fn published(&self, topic: &str) -> Vec<Vec<u8>> {
+        self.state()
+            .topics
+            .get(topic)
+            .map_or_else(Vec::new, |topic| {
+                topic
+                    .messages
+                    .iter()
+                    .map(|(message, _)| message.payload.clone())
+                    .collect()
+            })
+    }
+
+    /// Returns every message sent on IPC `channel`, received or not, in send
+    /// order.
+    pub fn ipc_sent(&self, channel: &str) -> Vec<Vec<u8>> {
+        self.state().sent.get(channel).cloned().unwrap_or_default()
+    }
+
+    /// Returns the current environment variables.
+    pub fn env_vars(&self) -> BTreeMap<String, String> {
+        self.state().env.clone()
+    }
+
+    /// Returns every log entry, oldest first.
+    pub fn logs(&self) -> Vec<LogRecord> {
+        self.state().logs.clone()
+    }
+
+    /// Returns every recorded metric sample, oldest first.
+    pub fn metrics(&self) -> Vec<(String, f64)> {
+        self.state().metrics.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+
+    /// Waits until `pop` returns a message, re-checking after every publish
+    /// or send.
+    async fn wait_for<T, F>(&self, mut pop: F) -> T
+    where
+        F: FnMut(&mut State) -> Option<T>,
+    {
+        loop {
+            let delivered = self.delivered.notified();
+            tokio::pin!(delivered);
+            delivered.as_mut().enable();
+            if let Some(msg) = pop(&mut self.state()) {
+                return msg;
+            }
+            delivered.await;
+        }
+    }
+}
+
+impl Backend for InMemoryExtio {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::from([
+            Capability::Files,
+            Capability::Storage,
+            Capability::Mq,
+            Capability::Ipc,
+            Capability::Clock,
+            Capability::Env,
+            Capability::Logging,
+            Capability::Metrics,
+        ])
+    }
+}
+
+fn is_root(path: &Path) -> bool {
+    path.parent().is_none() || path.as_os_str().is_empty()
+}
+
+fn not_found(path: &Path) -> ExtioError {
+    ExtioError::NotFound(path.display().to_string())
+}
+
+impl State {
+    fn node(&self, path: &Path) -> Result<&Node, ExtioError> {
+        self.files.get(path).ok_or_else(|| not_found(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        is_root(path) || self.files.get(path).is_some_and(|node| node.data.is_none())
+    }
+
+    fn check_parent(&self, path: &Path) -> Result<(), ExtioError> {
+        match path.parent() {
+            Some(parent) if !self.is_dir(parent) => match self.files.get(parent) {
+                Some(_) => Err(ExtioError::InvalidInput(format!(
+                    "{} is not a directory",
+                    parent.display()
+                ))),
+                None => Err(not_found(parent)),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn check_dir(&self, path: &Path) -> Result<(), ExtioError> {
+        if self.is_dir(path) {
+            Ok(())
+        } else {
+            self.node(path)?;
+            Err(ExtioError::InvalidInput(format!(
+                "{} is not a directory",
+                path.display()
+            )))
+        }
+    }
+
+    fn file(&self, path: &Path) -> Result<&Vec<u8>, ExtioError> {
+        self.node(path)?
+            .data
+            .as_ref()
+            .ok_or_else(|| ExtioError::InvalidInput(format!("{} is a directory", path.display())))
+    }
+
+    /// Looks up the file at `path` for writing, creating it if allowed.
+    fn writable(&mut self, path: &Path, create_new: bool) -> Result<&mut Node, ExtioError> {
+        self.check_parent(path)?;
+        if self.is_dir(path) {
+            return Err(ExtioError::InvalidInput(format!(
+                "{} is a directory",
+                path.display()
+            )));
+        }
+        match self.files.get(path) {
+            Some(_) if create_new => {
+                return Err(ExtioError::AlreadyExists(path.display().to_string()));
+            }
+            _ => self.check_readonly(path)?,
+        }
+        let node = self
+            .files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| Node::file(Vec::new()));
+        node.modified = SystemTime::now();
+        Ok(node)
+    }
+
+    /// Fails if the entry at `path` exists and is read-only.
+    fn check_readonly(&self, path: &Path) -> Result<(), ExtioError> {
+        match self.files.get(path) {
+            Some(node) if node.permissions.readonly => {
+                Err(ExtioError::PermissionDenied(path.display().to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Paths of `dir` and everything below it.
+    fn subtree(&self, dir: &Path) -> Vec<PathBuf> {
+        self.files
+            .keys()
+            .filter(|path| path.starts_with(dir))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl FileIo for InMemoryExtio {
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>, Self::Error> {
+        Ok(self.state().file(path)?.clone())
+    }
+
+    async fn write_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
+        self.write_file_with(path, data, WriteOptions::new()).await
+    }
+
+    async fn write_file_with(
+        &self,
+        path: &Path,
+        data: &[u8],
+        options: WriteOptions,
+    ) -> Result<(), Self::Error> {
+        // Every write happens under the state lock, so it is atomic.
+        let mut state = self.state();
+        state.writable(path, options.create_new)?.data = Some(data.to_vec());
+        Ok(())
+    }
+
+    async fn append_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        let node = state.writable(path, false)?;
+        node.data
+            .get_or_insert_with(Vec::new)
+            .extend_from_slice(data);
+        Ok(())
+    }
+
+    async fn delete_file(&self, path: &Path) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        state.file(path)?;
+        state.files.remove(path);
+        Ok(())
+    }
+
+    async fn stat(&self, path: &Path) -> Result<Metadata, Self::Error> {
+        let state = self.state();
+        if is_root(path) {
+            return Ok(Node::dir().metadata());
+        }
+        Ok(state.node(path)?.metadata())
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        let is_dir = state.node(from)?.data.is_none();
+        state.check_parent(to)?;
+        state.check_readonly(from)?;
+        state.check_readonly(to)?;
+        if from == to {
+            return Ok(());
+        }
+        if to.starts_with(from) {
+            return Err(ExtioError::InvalidInput(format!(
+                "cannot move {} into itself",
+                from.display()
+            )));
+        }
+        match state.files.get(to) {
+            Some(target) if target.data.is_none() => {
+                return Err(ExtioError::AlreadyExists(to.display().to_string()));
+            }
+            Some(_) if is_dir => {
+                return Err(ExtioError::InvalidInput(format!(
+                    "{} is not a directory",
+                    to.display()
+                )));
+            }
+            _ => {}
+        }
+        for path in state.subtree(from) {
+            let node = state.files.remove(&path).expect("path is in the tree");
+            let moved = to.join(path.strip_prefix(from).expect("path is below from"));
+            state.files.insert(moved, node);
+        }
+        Ok(())
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<u64, Self::Error> {
+        let mut state = self.state();
+        let data = state.file(from)?.clone();
+        let len = data.len() as u64;
+        state.writable(to, false)?.data = Some(data);
+        Ok(len)
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        let mut missing: Vec<&Path> = Vec::new();
+        for dir in path.ancestors().filter(|dir| !is_root(dir)) {
+            match state.files.get(dir) {
+                Some(node) if node.data.is_none() => break,
+                Some(_) => return Err(ExtioError::AlreadyExists(dir.display().to_string())),
+                None => missing.push(dir),
+            }
+        }
+        for dir in missing {
+            state.files.insert(dir.to_path_buf(), Node::dir());
+        }
+        Ok(())
+    }
+
+    async fn remove_dir_all(&self, path: &Path) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        if is_root(path) {
+            return Err(ExtioError::InvalidInput(
+                "cannot remove the root directory".to_owned(),
+            ));
+        }
+        state.check_dir(path)?;
+        for path in state.subtree(path) {
+            state.files.remove(&path);
+        }
+        Ok(())
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &Path,
+        permissions: Permissions,
+    ) -> Result<(), Self::Error> {
+        let mut state = self.state();
+        let node = state.files.get_mut(path).ok_or_else(|| not_found(path))?;
+        node.permissions = permissions;
+        Ok(())
+    }
+
+    async fn list_dir(&self, path: &Path) -> Result<Vec<DirEntry>, Self::Error> {
+        let state = self.state();
+        state.check_dir(path)?;
+        Ok(state
+            .files
+            .iter()
+            .filter(|(child, _)| child.parent() == Some(path))
+            .map(|(child, node)| DirEntry::new(child.clone(), node.metadata()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ObjectStore for InMemoryExtio {
+    async fn storage_put(&self, key: &str, data: Vec<u8>) -> Result<(), Self::Error> {
+        self.state().objects.insert(key.to_owned(), data);
+        Ok(())
+    }
+
+    async fn storage_get(&self, key: &str) -> Result<Vec<u8>, Self::Error> {
+        self.state()
+            .objects
+            .get(key)
+            .cloned()
+            .ok_or_else(|| ExtioError::NotFound(key.to_owned()))
+    }
+
+    async fn storage_delete(&self, key: &str) -> Result<(), Self::Error> {
+        self.state()
+            .objects
+            .remove(key)
+            .map(|_| ())
+            .ok_or_else(|| ExtioError::NotFound(key.to_owned()))
+    }
+}
+
+#[async_trait]
+impl MessageQueue for InMemoryExtio {
+    async fn mq_publish_with(&self, topic: &str, message: Message) -> Result<String, Self::Error> {
+        let id = {
+            let mut state = self.state();
+            let messages = &mut state.topics.entry(topic.to_owned()).or_default().messages;
+            messages.push((message, SystemTime::now()));
+            messages.len() - 1
+        };
+        self.delivered.notify_waiters();
+        Ok(id.to_string())
+    }
+
+    async fn mq_consume_group(
+        &self,
+        topic: &str,
+        group: &str,
+    ) -> Result<Delivery<Self::Error>, Self::Error> {
+        let (index, redeliveries, message, timestamp) = self
+            .wait_for(|state| {
+                let topic = state.topics.get_mut(topic)?;
+                let (index, redeliveries) = topic.next(group)?;
+                let (message, timestamp) = topic.messages[index].clone();
+                Some((index, redeliveries, message, timestamp))
+            })
+            .await;
+        let acker = MemoryAcker {
+            state: self.state.clone(),
+            delivered: self.delivered.clone(),
+            topic: topic.to_owned(),
+            group: group.to_owned(),
+            index,
+            settled: false,
+        };
+        Ok(Delivery::new(index.to_string(), message, timestamp, acker)
+            .with_redeliveries(redeliveries))
+    }
+}
+
+#[async_trait]
+impl Ipc for InMemoryExtio {
+    async fn ipc_send(&self, channel: &str, data: &[u8]) -> Result<(), Self::Error> {
+        {
+            let mut state = self.state();
+            let channel = channel.to_owned();
+            state
+                .sent
+                .entry(channel.clone())
+                .or_default()
+                .push(data.to_vec());
+            state
+                .channels
+                .entry(channel)
+                .or_default()
+                .push_back(data.to_vec());
+        }
+        self.delivered.notify_waiters();
+        Ok(())
+    }
+
+    async fn ipc_receive(&self, channel: &str) -> Result<Vec<u8>, Self::Error> {
+        Ok(self
+            .wait_for(|state| state.channels.get_mut(channel)?.pop_front())
+            .await)
+    }
+}
+
+impl Env for InMemoryExtio {
+    fn get_env(&self, key: &str) -> Option<String> {
+        self.state().env.get(key).cloned()
+    }
+
+    fn set_env(&self, key: &str, value: &str) -> Result<(), Self::Error> {
+        self.state().env.insert(key.to_owned(), value.to_owned());
+        Ok(())
+    }
+}
+
+impl Logger for InMemoryExtio {
+    fn log(&self, level: &str, msg: &str) {
+        self.state().logs.push(LogRecord {
+            level: level.to_owned(),
+            message: msg.to_owned(),
+        });
+    }
+}
+
+impl Metrics for InMemoryExtio {
+    fn record_metric(&self, name: &str, value: f64) {
+        self.state().metrics.push((name.to_owned(), value));
+    }
+}
+
+impl Clock for InMemoryExtio {}
+
+impl HttpClient for InMemoryExtio {}
+
+impl HttpServer for InMemoryExtio {}
+
+impl Tcp for InMemoryExtio {}
+
+impl Udp for InMemoryExtio {}
+
+impl WebSocket for InMemoryExtio {}
+
+impl Database for InMemoryExtio {}
+
+impl Exec for InMemoryExtio {}
+
+impl Crypto for InMemoryExtio {}
@@ -0,0 +1,33 @@
+//! Shipped backend implementations.
+
+#[cfg(feature = "hyper")]
+mod hyper_server;
+mod in_process_http;
+mod local_broker;
+#[cfg(feature = "local-fs")]
+mod local_fs;
+mod memory;
+#[cfg(feature = "reqwest")]
+mod reqwest_http;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod tokio_net;
+mod tokio_process;
+#[cfg(feature = "websocket")]
+mod tungstenite_ws;
+
+#[cfg(feature = "hyper")]
+pub use self::hyper_server::HyperServer;
+pub use self::in_process_http::InProcessHttp;
+pub use self::local_broker::{LocalBroker, LocalBrokerBuilder};
+#[cfg(feature = "local-fs")]
+pub use self::local_fs::LocalFs;
+pub use self::memory::{InMemoryExtio, LogRecord};
+#[cfg(feature = "reqwest")]
+pub use self::reqwest_http::{ReqwestHttp, ReqwestHttpBuilder};
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::{SqliteDb, SqliteDbBuilder};
+pub use self::tokio_net::TokioNet;
+pub use self::tokio_process::TokioProcess;
+#[cfg(feature = "websocket")]
+pub use self::tungstenite_ws::{TungsteniteConnection, TungsteniteWs};
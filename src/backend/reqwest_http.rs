@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use http::{Request, Response};
+use reqwest::{Certificate, Client, Proxy};
+
+use crate::net::Body;
+use crate::{Backend, Capabilities, Capability, ExtioError, HttpClient};
+
+/// [`HttpClient`] backend built on `reqwest`.
+///
+/// Holds one pooled `reqwest::Client`, so connections are reused across
+/// requests; cloning a `ReqwestHttp` shares the pool. Non-2xx responses are
+/// returned as responses, not errors; transport failures are mapped onto
+/// [`ExtioError`] with the underlying `reqwest::Error` kept as the source.
+#[derive(Debug, Clone)]
+pub struct ReqwestHttp {
+    client: Client,
+}
+
+impl ReqwestHttp {
+    /// Creates a backend with reqwest's default settings.
+    pub fn new() -> Result<Self, ExtioError> {
+        ReqwestHttp::builder().build()
+    }
+
+    /// Returns a builder for configuring timeouts, proxy and TLS.
+    pub fn builder() -> ReqwestHttpBuilder {
+        ReqwestHttpBuilder::default()
+    }
+
+    /// Wraps an already configured client.
+    pub fn from_client(client: Client) -> Self {
+        ReqwestHttp { client }
+    }
+
+    /// Returns the underlying client.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Builder for [`ReqwestHttp`].
+#[derive(Debug, Default)]
+pub struct ReqwestHttpBuilder {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    proxy: Option<String>,
+    no_proxy: bool,
+    root_certificates: Vec<Vec<u8>>,
+    accept_invalid_certs: bool,
+    user_agent: Option<String>,
+}
+
+impl ReqwestHttpBuilder {
+    /// Total timeout for a request, from connecting until the body is read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// How long idle pooled connections are kept.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum idle pooled connections per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Routes all requests through the proxy at `url`.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Ignores proxies configured through the environment.
+    pub fn no_proxy(mut self) -> Self {
+        self.no_proxy = true;
+        self
+    }
+
+    /// Trusts an additional PEM-encoded root certificate.
+    pub fn add_root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Disables TLS certificate verification. Only for tests.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Builds the backend.
+    ///
+    /// Fails with [`ExtioError::InvalidInput`] for a malformed proxy URL or
+    /// certificate.
+    pub fn build(self) -> Result<ReqwestHttp, ExtioError> {
+        let mut builder = Client::builder();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(url) = &self.proxy {
+            builder = builder.proxy(Proxy::all(url)?);
+        }
+        if self.no_proxy {
+            builder = builder.no_proxy();
+        }
+        for pem in &self.root_certificates {
+            builder = builder.add_root_certificate(Certificate::from_pem(pem)?);
+        }
+        if let Some(user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        let client = builder
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .build()?;
+        Ok(ReqwestHttp { client })
+    }
+}
+
+impl Backend for ReqwestHttp {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capability::Http.into()
+    }
+}
+
+#[async_trait]
+impl HttpClient for ReqwestHttp {
+    async fn http_request(&self, req: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, Self::Error> {
+        let req = reqwest::Request::try_from(req)?;
+        let response = self.client.execute(req).await?;
+
+        let mut builder = Response::builder()
+            .status(response.status())
+            .version(response.version());
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(response.headers().clone());
+        }
+        let body = response.bytes().await?;
+
+        Ok(builder.body(body.to_vec())?)
+    }
+
+    async fn http_request_stream(&self, req: Request<Body>) -> Result<Response<Body>, Self::Error> {
+        let (parts, body) = req.into_parts();
+        let body = match body.as_bytes() {
+            Some(bytes) => reqwest::Body::from(bytes.clone()),
+            None => reqwest::Body::wrap_stream(body),
+        };
+        let req = reqwest::Request::try_from(Request::from_parts(parts, body))?;
+        let response = self.client.execute(req).await?;
+
+        let mut builder = Response::builder()
+            .status(response.status())
+            .version(response.version());
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(response.headers().clone());
+        }
+
+        Ok(builder.body(Body::from_stream(response.bytes_stream()))?)
+    }
+}
@@ -0,0 +1,430 @@
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use rusqlite::types::{ToSqlOutput, Value, ValueRef};
+use rusqlite::{Connection, ToSql, params_from_iter};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::db::{DbValue, ResultSet, Statement, Transaction, TransactionHandle};
+use crate::{Backend, Capabilities, Capability, Database, ExtioError};
+
+/// [`Database`] backend on a bundled SQLite, in memory or in a file.
+///
+/// Holds a single connection behind an async mutex; statements run on the
+/// blocking thread pool and cloning a `SqliteDb` shares the connection. A
+/// transaction keeps the connection to itself until it ends, so other calls
+/// wait for it.
+///
+/// Every statement goes through the connection's prepared-statement cache.
+/// `Bool` parameters are stored as `0`/`1` and `Timestamp` parameters as
+/// `YYYY-MM-DD HH:MM:SS[.fff]` UTC text, which SQLite's date functions
+/// understand. Columns declared with a type containing `BOOL`, or `DATE` or
+/// `TIME`, are read back as `Bool` and `Timestamp` respectively.
+#[derive(Debug, Clone)]
+pub struct SqliteDb {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDb {
+    /// Opens (creating if needed) the database file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ExtioError> {
+        SqliteDb::builder().open(path)
+    }
+
+    /// Opens a private in-memory database, discarded when the last clone is
+    /// dropped.
+    pub fn memory() -> Result<Self, ExtioError> {
+        SqliteDb::builder().memory()
+    }
+
+    /// Returns a builder for configuring the connection.
+    pub fn builder() -> SqliteDbBuilder {
+        SqliteDbBuilder::default()
+    }
+
+    /// Wraps an already open connection.
+    pub fn from_connection(conn: Connection) -> Self {
+        SqliteDb {
+            conn: Arc::new(Mutex::new(conn)),
+        }
+    }
+
+    async fn run<T, F>(&self, f: F) -> Result<T, ExtioError>
+    where
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = TxConn(self.conn.clone().lock_owned().await);
+        let (_, result) = blocking(conn, f).await?;
+        Ok(result?)
+    }
+}
+
+/// Builder for [`SqliteDb`].
+#[derive(Debug, Default)]
+pub struct SqliteDbBuilder {
+    statement_cache_capacity: Option<usize>,
+    busy_timeout: Option<Duration>,
+}
+
+impl SqliteDbBuilder {
+    /// Number of prepared statements kept in the cache (SQLite default 16).
+    pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
+        self.statement_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// How long to wait for a lock held by another process before failing
+    /// with [`ErrorKind::Timeout`](crate::ErrorKind::Timeout).
+    pub fn busy_timeout(mut self, timeout: Duration) -> Self {
+        self.busy_timeout = Some(timeout);
+        self
+    }
+
+    /// Opens (creating if needed) the database file at `path`.
+    pub fn open(self, path: impl AsRef<Path>) -> Result<SqliteDb, ExtioError> {
+        self.configure(Connection::open(path)?)
+    }
+
+    /// Opens a private in-memory database.
+    pub fn memory(self) -> Result<SqliteDb, ExtioError> {
+        self.configure(Connection::open_in_memory()?)
+    }
+
+    fn configure(self, conn: Connection) -> Result<SqliteDb, ExtioError> {
+        if let Some(capacity) = self.statement_cache_capacity {
+            conn.set_prepared_statement_cache_capacity(capacity);
+        }
+        if let Some(timeout) = self.busy_timeout {
+            conn.busy_timeout(timeout)?;
+        }
+        Ok(SqliteDb::from_connection(conn))
+    }
+}
+
+impl Backend for SqliteDb {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capability::Db.into()
+    }
+}
+
+#[async_trait]
+impl Database for SqliteDb {
+    async fn db_query(&self, query: &str, params: &[DbValue]) -> Result<ResultSet, Self::Error> {
+        let (query, params) = (query.to_owned(), params.to_vec());
+        self.run(move |conn| query_rows(conn, &query, &params))
+            .await
+    }
+
+    /// Scripts of several statements are accepted when `params` is empty;
+    /// the count is then that of the last statement.
+    async fn db_execute(&self, query: &str, params: &[DbValue]) -> Result<u64, Self::Error> {
+        let (query, params) = (query.to_owned(), params.to_vec());
+        self.run(move |conn| execute(conn, &query, &params)).await
+    }
+
+    async fn db_begin(&self) -> Result<TransactionHandle<Self::Error>, Self::Error> {
+        let conn = TxConn(self.conn.clone().lock_owned().await);
+        let (conn, result) = blocking(conn, |conn| conn.execute_batch("BEGIN")).await?;
+        result?;
+        Ok(Box::new(SqliteTransaction { conn: Some(conn) }))
+    }
+
+    async fn db_prepare(&self, query: &str) -> Result<Statement, Self::Error> {
+        let query = query.to_owned();
+        self.run(move |conn| {
+            let stmt = conn.prepare_cached(&query)?;
+            let (params, columns) = (stmt.parameter_count(), stmt.column_names());
+            let columns: Vec<String> = columns.into_iter().map(str::to_owned).collect();
+            Ok(Statement::new(query)
+                .with_params(params)
+                .with_columns(columns))
+        })
+        .await
+    }
+}
+
+/// Transaction holding the connection lock until it ends.
+struct SqliteTransaction {
+    /// `None` only if a statement panicked, which also rolled back.
+    conn: Option<TxConn>,
+}
+
+impl SqliteTransaction {
+    async fn run<T, F>(&mut self, f: F) -> Result<T, ExtioError>
+    where
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = self
+            .conn
+            .take()
+            .ok_or_else(|| ExtioError::backend("transaction was aborted"))?;
+        let (conn, result) = blocking(conn, f).await?;
+        self.conn = Some(conn);
+        Ok(result?)
+    }
+}
+
+#[async_trait]
+impl Transaction for SqliteTransaction {
+    type Error = ExtioError;
+
+    async fn query(&mut self, query: &str, params: &[DbValue]) -> Result<ResultSet, Self::Error> {
+        let (query, params) = (query.to_owned(), params.to_vec());
+        self.run(move |conn| query_rows(conn, &query, &params))
+            .await
+    }
+
+    async fn execute(&mut self, query: &str, params: &[DbValue]) -> Result<u64, Self::Error> {
+        let (query, params) = (query.to_owned(), params.to_vec());
+        self.run(move |conn| execute(conn, &query, &params)).await
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<(), Self::Error> {
+        self.run(|conn| conn.execute_batch("COMMIT")).await
+    }
+
+    async fn rollback(mut self: Box<Self>) -> Result<(), Self::Error> {
+        self.run(|conn| conn.execute_batch("ROLLBACK")).await
+    }
+}
+
+/// Locked connection that rolls back any transaction still open when it is
+/// released, so a dropped or panicked transaction never leaks into the next
+/// caller.
+struct TxConn(OwnedMutexGuard<Connection>);
+
+impl Deref for TxConn {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.0
+    }
+}
+
+impl Drop for TxConn {
+    fn drop(&mut self) {
+        if !self.0.is_autocommit() {
+            let _ = self.0.execute_batch("ROLLBACK");
+        }
+    }
+}
+
+/// Runs `f` on the blocking pool, handing the connection back afterwards.
+async fn blocking<T, F>(conn: TxConn, f: F) -> Result<(TxConn, rusqlite::Result<T>), ExtioError>
+where
+    F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let result = f(&conn);
+        (conn, result)
+    })
+    .await
+    .map_err(ExtioError::backend)
+}
+
+fn query_rows(conn: &Connection, query: &str, params: &[DbValue]) -> rusqlite::Result<ResultSet> {
+    let mut stmt = conn.prepare_cached(query)?;
+    let (names, hints): (Vec<String>, Vec<Hint>) = stmt
+        .columns()
+        .iter()
+        .map(|column| {
+            (
+                column.name().to_owned(),
+                Hint::from_decl(column.decl_type()),
+            )
+        })
+        .unzip();
+    let mut result = ResultSet::new(names);
+    let mut rows = stmt.query(params_from_iter(params.iter().map(Param)))?;
+    while let Some(row) = rows.next()? {
+        let values = hints
+            .iter()
+            .enumerate()
+            .map(|(i, hint)| Ok(hint.decode(row.get_ref(i)?)))
+            .collect::<rusqlite::Result<_>>()?;
+        result.push(values);
+    }
+    Ok(result)
+}
+
+fn execute(conn: &Connection, query: &str, params: &[DbValue]) -> rusqlite::Result<u64> {
+    let changed = match conn.prepare_cached(query) {
+        Ok(mut stmt) => stmt.execute(params_from_iter(params.iter().map(Param)))?,
+        Err(rusqlite::Error::MultipleStatement) if params.is_empty() => {
+            conn.execute_batch(query)?;
+            conn.changes() as usize
+        }
+        Err(err) => return Err(err),
+    };
+    Ok(changed as u64)
+}
+
+/// Binds a [`DbValue`] as a statement parameter.
+struct Param<'a>(&'a DbValue);
+
+impl ToSql for Param<'_> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(match self.0 {
+            DbValue::Null => ToSqlOutput::Borrowed(ValueRef::Null),
+            DbValue::Bool(v) => ToSqlOutput::Owned(Value::Integer((*v).into())),
+            DbValue::Int(v) => ToSqlOutput::Owned(Value::Integer(*v)),
+            DbValue::Float(v) => ToSqlOutput::Owned(Value::Real(*v)),
+            DbValue::Text(v) => ToSqlOutput::Borrowed(ValueRef::Text(v.as_bytes())),
+            DbValue::Bytes(v) => ToSqlOutput::Borrowed(ValueRef::Blob(v)),
+            DbValue::Timestamp(v) => ToSqlOutput::Owned(Value::Text(format_timestamp(*v))),
+        })
+    }
+}
+
+/// How to read a column back, from its declared type.
+#[derive(Clone, Copy)]
+enum Hint {
+    None,
+    Bool,
+    Timestamp,
+}
+
+impl Hint {
+    fn from_decl(decl: Option<&str>) -> Self {
+        let decl = decl.unwrap_or_default().to_ascii_uppercase();
+        if decl.contains("BOOL") {
+            Hint::Bool
+        } else if decl.contains("DATE") || decl.contains("TIME") {
+            Hint::Timestamp
+        } else {
+            Hint::None
+        }
+    }
+
+    fn decode(self, value: ValueRef<'_>) -> DbValue {
+        match (self, value) {
+            (_, ValueRef::Null) => DbValue::Null,
+            (Hint::Bool, ValueRef::Integer(v @ (0 | 1))) => DbValue::Bool(v == 1),
+            (Hint::Timestamp, ValueRef::Integer(secs)) => match from_unix(secs, 0) {
+                Some(time) => DbValue::Timestamp(time),
+                None => DbValue::Int(secs),
+            },
+            (_, ValueRef::Integer(v)) => DbValue::Int(v),
+            (_, ValueRef::Real(v)) => DbValue::Float(v),
+            (hint, ValueRef::Text(bytes)) => match String::from_utf8(bytes.to_vec()) {
+                Ok(text) => match hint {
+                    Hint::Timestamp => {
+                        parse_timestamp(&text).map_or(DbValue::Text(text), DbValue::Timestamp)
+                    }
+                    _ => DbValue::Text(text),
+                },
+                Err(err) => DbValue::Bytes(err.into_bytes()),
+            },
+            (_, ValueRef::Blob(bytes)) => DbValue::Bytes(bytes.to_vec()),
+        }
+    }
+}
+
+/// Formats `time` as UTC `YYYY-MM-DD HH:MM:SS[.fff]`.
+fn format_timestamp(time: SystemTime) -> String {
+    let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+        Err(err) => {
+            let d = err.duration();
+            match d.subsec_nanos() {
+                0 => (-(d.as_secs() as i64), 0),
+                n => (-(d.as_secs() as i64) - 1, 1_000_000_000 - n),
+            }
+        }
+    };
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let secs = secs.rem_euclid(86_400);
+    let mut out = format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    );
+    if nanos != 0 {
+        out.push('.');
+        out.push_str(format!("{nanos:09}").trim_end_matches('0'));
+    }
+    out
+}
+
+/// Parses `YYYY-MM-DD[( |T)HH:MM[:SS[.fff]]][Z]` as UTC.
+fn parse_timestamp(text: &str) -> Option<SystemTime> {
+    let text = text.strip_suffix('Z').unwrap_or(text);
+    let (date, time) = match text.split_once([' ', 'T']) {
+        Some((date, time)) => (date, time),
+        None => (text, "00:00"),
+    };
+
+    let mut date = date.split('-');
+    let year = number(date.next()?, 4)?;
+    let month = number(date.next()?, 2)?;
+    let day = number(date.next()?, 2)?;
+    if date.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time = time.split(':');
+    let hour = number(time.next()?, 2)?;
+    let minute = number(time.next()?, 2)?;
+    let second = time.next().map_or(Some(0), |s| number(s, 2))?;
+    if time.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = format!("{fraction:0<9}").parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    from_unix(days * 86_400 + hour * 3600 + minute * 60 + second, nanos)
+}
+
+fn number(digits: &str, len: usize) -> Option<i64> {
+    if digits.len() != len || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn from_unix(secs: i64, nanos: u32) -> Option<SystemTime> {
+    let base = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+    };
+    base.checked_add(Duration::from_nanos(nanos.into()))
+}
+
+/// Days since 1970-01-01 to a proleptic Gregorian date.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Inverse of [`civil_from_days`].
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,115 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use async_trait::async_trait;
+use tokio::net::{TcpListener, TcpStream, UdpSocket as TokioUdpSocket};
+
+use crate::net::{TcpAcceptor, TcpConnection, TcpListenerHandle, UdpSocket, UdpSocketHandle};
+use crate::{Backend, Capabilities, Capability, ExtioError, Tcp, Udp};
+
+/// TCP and UDP backend built on `tokio::net`.
+#[derive(Debug, Clone, Default)]
+pub struct TokioNet;
+
+impl TokioNet {
+    /// Creates a new socket backend.
+    pub fn new() -> Self {
+        TokioNet
+    }
+}
+
+impl Backend for TokioNet {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::from([Capability::Tcp, Capability::Udp])
+    }
+}
+
+#[async_trait]
+impl Tcp for TokioNet {
+    async fn tcp_connect(&self, addr: &str) -> Result<TcpConnection, Self::Error> {
+        let stream = TcpStream::connect(addr).await?;
+        stream.set_nodelay(true)?;
+        Ok(connection(stream)?)
+    }
+
+    async fn tcp_listen(&self, addr: &str) -> Result<TcpListenerHandle<Self::Error>, Self::Error> {
+        Ok(Box::new(TokioListener(TcpListener::bind(addr).await?)))
+    }
+}
+
+fn connection(stream: TcpStream) -> std::io::Result<TcpConnection> {
+    let local_addr = stream.local_addr()?;
+    let peer_addr = stream.peer_addr()?;
+    Ok(TcpConnection::with_addrs(stream, local_addr, peer_addr))
+}
+
+struct TokioListener(TcpListener);
+
+#[async_trait]
+impl TcpAcceptor for TokioListener {
+    type Error = ExtioError;
+
+    async fn accept(&mut self) -> Result<(TcpConnection, SocketAddr), Self::Error> {
+        let (stream, peer_addr) = self.0.accept().await?;
+        Ok((connection(stream)?, peer_addr))
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr, Self::Error> {
+        Ok(self.0.local_addr()?)
+    }
+}
+
+#[async_trait]
+impl Udp for TokioNet {
+    async fn udp_bind(&self, addr: &str) -> Result<UdpSocketHandle<Self::Error>, Self::Error> {
+        Ok(Box::new(TokioUdp(TokioUdpSocket::bind(addr).await?)))
+    }
+}
+
+struct TokioUdp(TokioUdpSocket);
+
+#[async_trait]
+impl UdpSocket for TokioUdp {
+    type Error = ExtioError;
+
+    async fn send_to(&self, data: &[u8], target: SocketAddr) -> Result<usize, Self::Error> {
+        Ok(self.0.send_to(data, target).await?)
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr), Self::Error> {
+        Ok(self.0.recv_from(buf).await?)
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr, Self::Error> {
+        Ok(self.0.local_addr()?)
+    }
+
+    fn set_broadcast(&self, on: bool) -> Result<(), Self::Error> {
+        Ok(self.0.set_broadcast(on)?)
+    }
+
+    fn join_multicast_v4(&self, group: Ipv4Addr, interface: Ipv4Addr) -> Result<(), Self::Error> {
+        Ok(self.0.join_multicast_v4(group, interface)?)
+    }
+
+    fn leave_multicast_v4(&self, group: Ipv4Addr, interface: Ipv4Addr) -> Result<(), Self::Error> {
+        Ok(self.0.leave_multicast_v4(group, interface)?)
+    }
+
+    fn join_multicast_v6(&self, group: &Ipv6Addr, interface: u32) -> Result<(), Self::Error> {
+        Ok(self.0.join_multicast_v6(group, interface)?)
+    }
+
+    fn leave_multicast_v6(&self, group: &Ipv6Addr, interface: u32) -> Result<(), Self::Error> {
+        Ok(self.0.leave_multicast_v6(group, interface)?)
+    }
+
+    fn set_multicast_loop_v4(&self, on: bool) -> Result<(), Self::Error> {
+        Ok(self.0.set_multicast_loop_v4(on)?)
+    }
+
+    fn set_multicast_ttl_v4(&self, ttl: u32) -> Result<(), Self::Error> {
+        Ok(self.0.set_multicast_ttl_v4(ttl)?)
+    }
+}
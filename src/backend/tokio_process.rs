@@ -0,0 +1,286 @@
+use std::io;
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{oneshot, watch};
+use tokio::time::Instant;
+
+use crate::process::{ChildControl, ChildHandle, ExecOptions, ExecOutput, ExitStatus};
+use crate::{Backend, Capabilities, Capability, Exec, ExtioError};
+
+/// [`Exec`] backend built on `tokio::process`.
+///
+/// Children are killed if the call is cancelled or times out, or when their
+/// [`ChildHandle`] is dropped, so nothing is left running by accident.
+/// [`ResourceLimits`](crate::process::ResourceLimits) are applied with
+/// `setrlimit` in the child; on other platforms setting one is unsupported.
+#[derive(Debug, Clone, Default)]
+pub struct TokioProcess;
+
+impl TokioProcess {
+    /// Creates a new process backend.
+    pub fn new() -> Self {
+        TokioProcess
+    }
+}
+
+impl Backend for TokioProcess {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capability::Exec.into()
+    }
+}
+
+#[async_trait]
+impl Exec for TokioProcess {
+    async fn exec_with(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ExecOutput, Self::Error> {
+        let mut command = command(cmd, args, options)?;
+        command
+            .stdin(if options.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = command.spawn()?;
+
+        let feed = feed(
+            child.stdin.take(),
+            options.stdin.as_deref().unwrap_or_default(),
+        );
+        let run = async {
+            let (fed, output) = tokio::join!(feed, child.wait_with_output());
+            fed?;
+            output
+        };
+
+        let output = match options.timeout {
+            Some(limit) => tokio::time::timeout(limit, run)
+                .await
+                .map_err(|_| timed_out(cmd, limit))??,
+            None => run.await?,
+        };
+        Ok(ExecOutput {
+            status: output.status.into(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    async fn spawn(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ChildHandle<Self::Error>, Self::Error> {
+        let mut command = command(cmd, args, options)?;
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = command.spawn()?;
+
+        let stdin = match &options.stdin {
+            Some(input) => {
+                let (stdin, input) = (child.stdin.take(), input.clone());
+                tokio::spawn(async move { feed(stdin, &input).await });
+                None
+            }
+            None => child.stdin.take().map(|s| Box::pin(s) as _),
+        };
+        let stdout = child.stdout.take().map(|s| Box::pin(s) as _);
+        let stderr = child.stderr.take().map(|s| Box::pin(s) as _);
+
+        let (kill, kill_rx) = oneshot::channel();
+        let (exit_tx, exit) = watch::channel(None);
+        let id = child.id();
+        let deadline = options.timeout.map(|limit| Instant::now() + limit);
+        tokio::spawn(supervise(child, deadline, kill_rx, exit_tx));
+
+        let mut handle = ChildHandle::new(TokioChild {
+            id,
+            cmd: cmd.to_owned(),
+            limit: options.timeout,
+            kill: Some(kill),
+            exit,
+        });
+        handle.stdin = stdin;
+        handle.stdout = stdout;
+        handle.stderr = stderr;
+        Ok(handle)
+    }
+}
+
+/// How a spawned child ended.
+struct Exit {
+    status: io::Result<ExitStatus>,
+    timed_out: bool,
+}
+
+impl Exit {
+    fn status(&self) -> io::Result<ExitStatus> {
+        match &self.status {
+            Ok(status) => Ok(*status),
+            Err(err) => Err(io::Error::new(err.kind(), err.to_string())),
+        }
+    }
+}
+
+/// Owns a spawned child until it exits, killing it at the deadline or when
+/// asked to, or when the [`TokioChild`] goes away.
+///
+/// Running apart from the handle means the deadline holds even if nobody
+/// ever calls [`ChildHandle::wait`].
+async fn supervise(
+    mut child: Child,
+    deadline: Option<Instant>,
+    kill: oneshot::Receiver<()>,
+    exit: watch::Sender<Option<Exit>>,
+) {
+    let expired = async {
+        match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    };
+    let timed_out = tokio::select! {
+        status = child.wait() => {
+            exit.send_replace(Some(Exit {
+                status: status.map(Into::into),
+                timed_out: false,
+            }));
+            return;
+        }
+        // Sent by `kill`, or the handle was dropped.
+        _ = kill => false,
+        () = expired => true,
+    };
+    let status = match child.kill().await {
+        Ok(()) => child.wait().await,
+        Err(err) => Err(err),
+    };
+    exit.send_replace(Some(Exit {
+        status: status.map(Into::into),
+        timed_out,
+    }));
+}
+
+/// [`ChildControl`] over a child owned by [`supervise`].
+struct TokioChild {
+    id: Option<u32>,
+    cmd: String,
+    limit: Option<Duration>,
+    kill: Option<oneshot::Sender<()>>,
+    exit: watch::Receiver<Option<Exit>>,
+}
+
+impl TokioChild {
+    /// Waits for [`supervise`] to report the exit.
+    async fn exited(&mut self) -> io::Result<Exit> {
+        let exit = self
+            .exit
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|_| io::Error::other(format!("lost track of `{}`", self.cmd)))?;
+        let exit = exit.as_ref().expect("waited for an exit");
+        Ok(Exit {
+            status: exit.status(),
+            timed_out: exit.timed_out,
+        })
+    }
+}
+
+#[async_trait]
+impl ChildControl for TokioChild {
+    type Error = ExtioError;
+
+    fn id(&self) -> Option<u32> {
+        match *self.exit.borrow() {
+            Some(_) => None,
+            None => self.id,
+        }
+    }
+
+    async fn wait(&mut self) -> Result<ExitStatus, Self::Error> {
+        let exit = self.exited().await?;
+        match self.limit {
+            Some(limit) if exit.timed_out => Err(timed_out(&self.cmd, limit).into()),
+            _ => Ok(exit.status?),
+        }
+    }
+
+    fn try_wait(&mut self) -> Result<Option<ExitStatus>, Self::Error> {
+        match &*self.exit.borrow() {
+            Some(exit) => Ok(Some(exit.status()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn kill(&mut self) -> Result<(), Self::Error> {
+        if let Some(kill) = self.kill.take() {
+            // Fails only if the child already exited.
+            let _ = kill.send(());
+        }
+        self.exited().await?.status?;
+        Ok(())
+    }
+}
+
+/// Builds the command with `options` applied, except stdio.
+fn command(cmd: &str, args: &[&str], options: &ExecOptions) -> Result<Command, ExtioError> {
+    let mut command = Command::new(cmd);
+    command.args(args).kill_on_drop(true);
+    if let Some(dir) = &options.cwd {
+        command.current_dir(dir);
+    }
+    if options.env_clear {
+        command.env_clear();
+    }
+    for (key, value) in &options.env {
+        match value {
+            Some(value) => command.env(key, value),
+            None => command.env_remove(key),
+        };
+    }
+    if !options.limits.is_empty() {
+        #[cfg(unix)]
+        {
+            let limits = options.limits;
+            // SAFETY: the hook only calls `setrlimit`, which is safe between
+            // fork and exec.
+            unsafe { command.pre_exec(move || limits.apply()) };
+        }
+        #[cfg(not(unix))]
+        return Err(crate::Unsupported::new("resource limits").into());
+    }
+    Ok(command)
+}
+
+/// Writes `input` to the child's stdin, if piped, then closes it.
+async fn feed(stdin: Option<ChildStdin>, input: &[u8]) -> io::Result<()> {
+    if let Some(mut stdin) = stdin {
+        match stdin.write_all(input).await {
+            // The child may exit or close stdin without reading it all.
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn timed_out(cmd: &str, limit: Duration) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("`{cmd}` did not exit within {limit:?}"),
+    )
+}
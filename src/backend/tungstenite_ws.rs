@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame as TungsteniteCloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::net::{CloseFrame, WsConnection, WsMessage, WsStream};
+use crate::{Backend, Capabilities, Capability, ExtioError, WebSocket};
+
+/// [`WebSocket`] backend built on `tokio-tungstenite`.
+///
+/// Supports `ws://` and, through native TLS, `wss://` URLs.
+#[derive(Debug, Clone, Default)]
+pub struct TungsteniteWs;
+
+impl TungsteniteWs {
+    /// Creates a new WebSocket backend.
+    pub fn new() -> Self {
+        TungsteniteWs
+    }
+}
+
+impl Backend for TungsteniteWs {
+    type Error = ExtioError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capability::Ws.into()
+    }
+}
+
+#[async_trait]
+impl WebSocket for TungsteniteWs {
+    async fn ws_connect(&self, url: &str) -> Result<WsStream<Self::Error>, Self::Error> {
+        let (stream, _) = tokio_tungstenite::connect_async(url).await?;
+        Ok(Box::new(TungsteniteConnection::new(stream)))
+    }
+}
+
+/// A [`WsConnection`] over any tokio-tungstenite stream.
+///
+/// Returned by [`TungsteniteWs::ws_connect`]; servers can also wrap
+/// accepted streams with [`TungsteniteConnection::new`].
+#[derive(Debug)]
+pub struct TungsteniteConnection<S = MaybeTlsStream<TcpStream>> {
+    inner: WebSocketStream<S>,
+}
+
+impl<S> TungsteniteConnection<S> {
+    /// Wraps an established tokio-tungstenite stream.
+    pub fn new(inner: WebSocketStream<S>) -> Self {
+        TungsteniteConnection { inner }
+    }
+}
+
+#[async_trait]
+impl<S> WsConnection for TungsteniteConnection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    type Error = ExtioError;
+
+    async fn send(&mut self, msg: WsMessage) -> Result<(), Self::Error> {
+        Ok(self.inner.send(into_message(msg)).await?)
+    }
+
+    async fn receive(&mut self) -> Result<Option<WsMessage>, Self::Error> {
+        use tokio_tungstenite::tungstenite::Error;
+
+        loop {
+            let msg = match self.inner.next().await {
+                None | Some(Err(Error::ConnectionClosed)) => return Ok(None),
+                Some(msg) => msg?,
+            };
+            if let Some(msg) = from_message(msg) {
+                return Ok(Some(msg));
+            }
+        }
+    }
+
+    async fn close(&mut self, frame: Option<CloseFrame>) -> Result<(), Self::Error> {
+        Ok(self.inner.close(frame.map(into_close_frame)).await?)
+    }
+}
+
+fn into_message(msg: WsMessage) -> Message {
+    match msg {
+        WsMessage::Text(text) => Message::text(text),
+        WsMessage::Binary(data) => Message::binary(data),
+        WsMessage::Ping(data) => Message::Ping(data.into()),
+        WsMessage::Pong(data) => Message::Pong(data.into()),
+        WsMessage::Close(frame) => Message::Close(frame.map(into_close_frame)),
+    }
+}
+
+fn into_close_frame(frame: CloseFrame) -> TungsteniteCloseFrame {
+    TungsteniteCloseFrame {
+        code: CloseCode::from(frame.code),
+        reason: frame.reason.into(),
+    }
+}
+
+/// Converts a received message; raw frames are never surfaced by the reader
+/// and are skipped.
+fn from_message(msg: Message) -> Option<WsMessage> {
+    Some(match msg {
+        Message::Text(text) => WsMessage::Text(text.as_str().to_owned()),
+        Message::Binary(data) => WsMessage::Binary(data.to_vec()),
+        Message::Ping(data) => WsMessage::Ping(data.to_vec()),
+        Message::Pong(data) => WsMessage::Pong(data.to_vec()),
+        Message::Close(frame) => WsMessage::Close(frame.map(|frame| CloseFrame {
+            code: frame.code.into(),
+            reason: frame.reason.as_str().to_owned(),
+        })),
+        Message::Frame(_) => return None,
+    })
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+
+/// A single capability a backend can provide.
+///
+/// Each variant corresponds to a capability trait (or, for [`Crypto`], one
+/// half of it).
+///
+/// [`Crypto`]: crate::Crypto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Capability {
+    /// [`FileIo`](crate::FileIo).
+    Files,
+    /// [`ObjectStore`](crate::ObjectStore).
+    Storage,
+    /// [`HttpClient`](crate::HttpClient).
+    Http,
+    /// [`HttpServer`](crate::HttpServer).
+    HttpServer,
+    /// [`Tcp`](crate::Tcp).
+    Tcp,
+    /// [`Udp`](crate::Udp).
+    Udp,
+    /// [`WebSocket`](crate::WebSocket).
+    Ws,
+    /// [`Database`](crate::Database).
+    Db,
+    /// [`Exec`](crate::Exec).
+    Exec,
+    /// [`MessageQueue`](crate::MessageQueue).
+    Mq,
+    /// [`Ipc`](crate::Ipc).
+    Ipc,
+    /// [`Clock`](crate::Clock).
+    Clock,
+    /// [`Env`](crate::Env).
+    Env,
+    /// [`Logger`](crate::Logger).
+    Logging,
+    /// [`Metrics`](crate::Metrics).
+    Metrics,
+    /// [`Crypto::get_secret`](crate::Crypto::get_secret).
+    Secrets,
+    /// [`Crypto::sign`](crate::Crypto::sign) and
+    /// [`Crypto::verify`](crate::Crypto::verify).
+    Signing,
+}
+
+impl Capability {
+    /// Every capability, in declaration order.
+    pub const ALL: [Capability; 17] = [
+        Capability::Files,
+        Capability::Storage,
+        Capability::Http,
+        Capability::HttpServer,
+        Capability::Tcp,
+        Capability::Udp,
+        Capability::Ws,
+        Capability::Db,
+        Capability::Exec,
+        Capability::Mq,
+        Capability::Ipc,
+        Capability::Clock,
+        Capability::Env,
+        Capability::Logging,
+        Capability::Metrics,
+        Capability::Secrets,
+        Capability::Signing,
+    ];
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// The set of capabilities a backend provides.
+///
+/// Returned by [`Backend::capabilities`](crate::Backend::capabilities) so
+/// generic code can check what a backend supports before calling it.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    /// An empty set.
+    pub const fn empty() -> Self {
+        Capabilities(0)
+    }
+
+    /// Returns `true` if `cap` is in the set.
+    pub fn contains(&self, cap: Capability) -> bool {
+        self.0 & cap.bit() != 0
+    }
+
+    /// Returns `true` if every capability in `other` is in the set.
+    pub fn contains_all(&self, other: Capabilities) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Adds `cap` to the set.
+    pub fn insert(&mut self, cap: Capability) {
+        self.0 |= cap.bit();
+    }
+
+    /// Removes `cap` from the set.
+    pub fn remove(&mut self, cap: Capability) {
+        self.0 &= !cap.bit();
+    }
+
+    /// Returns a copy of the set with `cap` added.
+    pub fn with(mut self, cap: Capability) -> Self {
+        self.insert(cap);
+        self
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the capabilities in `required` that are missing from the set.
+    pub fn missing(&self, required: Capabilities) -> Capabilities {
+        Capabilities(required.0 & !self.0)
+    }
+
+    /// Iterates over the capabilities in the set.
+    pub fn iter(&self) -> impl Iterator<Item = Capability> + '_ {
+        Capability::ALL
+            .into_iter()
+            .filter(|cap| self.contains(*cap))
+    }
+}
+
+impl From<Capability> for Capabilities {
+    fn from(cap: Capability) -> Self {
+        Capabilities(cap.bit())
+    }
+}
+
+impl FromIterator<Capability> for Capabilities {
+    fn from_iter<I: IntoIterator<Item = Capability>>(iter: I) -> Self {
+        let mut caps = Capabilities::empty();
+        for cap in iter {
+            caps.insert(cap);
+        }
+        caps
+    }
+}
+
+impl<const N: usize> From<[Capability; N]> for Capabilities {
+    fn from(caps: [Capability; N]) -> Self {
+        caps.into_iter().collect()
+    }
+}
+
+impl std::ops::BitOr for Capabilities {
+    type Output = Capabilities;
+
+    fn bitor(self, rhs: Capabilities) -> Capabilities {
+        Capabilities(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOr<Capability> for Capabilities {
+    type Output = Capabilities;
+
+    fn bitor(self, rhs: Capability) -> Capabilities {
+        self.with(rhs)
+    }
+}
+
+impl fmt::Debug for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Error returned by capability methods the backend does not implement.
+///
+/// Every default method body returns this instead of panicking, so callers
+/// can fall back or fail gracefully. Backend error types convert from it via
+/// the `From<Unsupported>` bound on [`Backend::Error`](crate::Backend::Error).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    operation: &'static str,
+    message: Option<String>,
+}
+
+impl Unsupported {
+    /// Creates an error for the named operation.
+    pub const fn new(operation: &'static str) -> Self {
+        Unsupported {
+            operation,
+            message: None,
+        }
+    }
+
+    /// Replaces the default message, e.g. with the one reported by the OS.
+    pub(crate) fn with_message(mut self, message: String) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Name of the unsupported operation (e.g. `"db_query"`).
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => f.write_str(message),
+            None => write!(f, "{} is not supported by this backend", self.operation),
+        }
+    }
+}
+
+impl std::error::Error for Unsupported {}
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use crate::{Backend, Unsupported};
+
+/// Cryptography and secrets.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait Crypto: Backend {
+    /// Retrieves a secret value (e.g., API key) from secure storage.
+    async fn get_secret(&self, key: &str) -> Result<Vec<u8>, Self::Error> {
+        Err(Unsupported::new("get_secret").into())
+    }
+
+    /// Signs data using the backend's cryptographic key.
+    async fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Err(Unsupported::new("sign").into())
+    }
+
+    /// Verifies a digital signature.
+    async fn verify(&self, data: &[u8], sig: &[u8]) -> Result<bool, Self::Error> {
+        Err(Unsupported::new("verify").into())
+    }
+}
@@ -0,0 +1,390 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use super::{Database, TransactionHandle, identifier};
+use crate::{ErrorKind, FileIo, HasErrorKind};
+
+/// Table recording applied migrations unless [`Migrator::table`] says
+/// otherwise.
+pub const DEFAULT_MIGRATIONS_TABLE: &str = "_extio_migrations";
+
+/// One versioned schema change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    version: u64,
+    name: String,
+    up: String,
+    down: Option<String>,
+}
+
+impl Migration {
+    /// Creates a migration that can only be applied, not reverted.
+    ///
+    /// - `up`: SQL script; it may contain several statements if the backend
+    ///   accepts scripts without parameters.
+    pub fn new(version: u64, name: impl Into<String>, up: impl Into<String>) -> Self {
+        Migration {
+            version,
+            name: name.into(),
+            up: up.into(),
+            down: None,
+        }
+    }
+
+    /// Sets the script that reverts this migration.
+    pub fn with_down(mut self, down: impl Into<String>) -> Self {
+        self.down = Some(down.into());
+        self
+    }
+
+    /// Version; migrations run in ascending order.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Descriptive name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Script applying the migration.
+    pub fn up(&self) -> &str {
+        &self.up
+    }
+
+    /// Script reverting the migration, if any.
+    pub fn down(&self) -> Option<&str> {
+        self.down.as_deref()
+    }
+
+    /// Checksum of the up script, stored when the migration is applied.
+    ///
+    /// 64-bit FNV-1a as 16 hex digits; it detects edits to migrations that
+    /// have already run, not tampering.
+    pub fn checksum(&self) -> String {
+        let hash = self.up.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        format!("{hash:016x}")
+    }
+}
+
+/// A migration recorded in the migrations table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    /// Version of the migration.
+    pub version: u64,
+    /// Name it had when applied.
+    pub name: String,
+    /// [`Migration::checksum`] it had when applied.
+    pub checksum: String,
+}
+
+/// Applies and reverts [`Migration`]s against any [`Database`] backend.
+///
+/// Applied versions are recorded, with their checksums, in a table the
+/// migrator creates on first use. Each migration runs in its own
+/// transaction when the backend supports [`db_begin`](Database::db_begin),
+/// so a failing script leaves neither partial changes nor a record behind.
+/// Before anything runs, every applied migration is compared with its
+/// source; an edited or missing one stops the run with
+/// [`ErrorKind::InvalidInput`].
+///
+/// Migrations can be listed in code (e.g. with `include_str!`) or loaded
+/// with [`from_dir`](Self::from_dir) from files named
+/// `<version>_<name>.up.sql` and `<version>_<name>.down.sql`, or just
+/// `<version>_<name>.sql` for migrations without a down script.
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+    table: String,
+}
+
+impl Migrator {
+    /// Creates a migrator over `migrations`, in any order.
+    ///
+    /// Fails with `InvalidInput` if two migrations share a version.
+    pub fn new(migrations: impl IntoIterator<Item = Migration>) -> io::Result<Self> {
+        let mut migrations: Vec<_> = migrations.into_iter().collect();
+        migrations.sort_by_key(Migration::version);
+        if let Some(pair) = migrations
+            .windows(2)
+            .find(|pair| pair[0].version == pair[1].version)
+        {
+            return Err(invalid(format!(
+                "migration {} is defined twice",
+                pair[0].version
+            )));
+        }
+        Ok(Migrator {
+            migrations,
+            table: DEFAULT_MIGRATIONS_TABLE.to_owned(),
+        })
+    }
+
+    /// Builds migrations from `(file name, contents)` pairs.
+    ///
+    /// Files not ending in `.sql` are skipped. An up and a down script of
+    /// the same version must have the same name. Useful with `include_str!`
+    /// to embed migrations in the binary.
+    pub fn from_files<I, N, S>(files: I) -> io::Result<Self>
+    where
+        I: IntoIterator<Item = (N, S)>,
+        N: AsRef<str>,
+        S: Into<String>,
+    {
+        let mut ups = BTreeMap::new();
+        let mut downs = BTreeMap::new();
+        for (file, sql) in files {
+            let file = file.as_ref();
+            let Some(stem) = file.strip_suffix(".sql") else {
+                continue;
+            };
+            let (stem, scripts) = match stem.strip_suffix(".down") {
+                Some(stem) => (stem, &mut downs),
+                None => (stem.strip_suffix(".up").unwrap_or(stem), &mut ups),
+            };
+            let (version, name) = parse_stem(stem)
+                .ok_or_else(|| invalid(format!("`{file}` is not named <version>_<name>.sql")))?;
+            if scripts
+                .insert(version, (name.to_owned(), sql.into()))
+                .is_some()
+            {
+                return Err(invalid(format!("migration {version} is defined twice")));
+            }
+        }
+
+        let mut migrations = Vec::with_capacity(ups.len());
+        for (version, (name, up)) in ups {
+            let mut migration = Migration::new(version, name, up);
+            if let Some((down_name, down)) = downs.remove(&version) {
+                if down_name != migration.name {
+                    return Err(invalid(format!(
+                        "migration {version} is named `{}` but its down script `{down_name}`",
+                        migration.name
+                    )));
+                }
+                migration = migration.with_down(down);
+            }
+            migrations.push(migration);
+        }
+        if let Some(version) = downs.keys().next() {
+            return Err(invalid(format!(
+                "migration {version} has a down script but no up script"
+            )));
+        }
+        Migrator::new(migrations)
+    }
+
+    /// Loads migrations from the `.sql` files in `dir`.
+    ///
+    /// See [`from_files`](Self::from_files) for naming.
+    pub async fn from_dir<F: FileIo>(fs: &F, dir: &Path) -> Result<Self, F::Error> {
+        let mut files = Vec::new();
+        for entry in fs.list_dir(dir).await? {
+            if entry.is_file() && entry.name.ends_with(".sql") {
+                let sql = String::from_utf8(fs.read_file(&entry.path).await?).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{} is not UTF-8", entry.path.display()),
+                    )
+                })?;
+                files.push((entry.name, sql));
+            }
+        }
+        Ok(Migrator::from_files(files)?)
+    }
+
+    /// Name of the table recording applied migrations.
+    ///
+    /// Defaults to [`DEFAULT_MIGRATIONS_TABLE`].
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Known migrations, by ascending version.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Returns the applied migrations, by ascending version, creating the
+    /// migrations table if needed.
+    pub async fn applied<D: Database>(&self, db: &D) -> Result<Vec<AppliedMigration>, D::Error> {
+        let table = identifier(&self.table)?;
+        db.db_execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {table} (\
+                 version BIGINT PRIMARY KEY, \
+                 name TEXT NOT NULL, \
+                 checksum TEXT NOT NULL, \
+                 applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP)"
+            ),
+            &[],
+        )
+        .await?;
+        let rows = db
+            .db_query(
+                &format!("SELECT version, name, checksum FROM {table} ORDER BY version"),
+                &[],
+            )
+            .await?;
+        let mut applied = Vec::with_capacity(rows.len());
+        for row in &rows {
+            applied.push(AppliedMigration {
+                version: row.get_at(0).map_err(io::Error::from)?,
+                name: row.get_at(1).map_err(io::Error::from)?,
+                checksum: row.get_at(2).map_err(io::Error::from)?,
+            });
+        }
+        Ok(applied)
+    }
+
+    /// Migrations not applied yet, by ascending version.
+    pub async fn pending<D: Database>(&self, db: &D) -> Result<Vec<&Migration>, D::Error> {
+        let applied = self.verified(db).await?;
+        Ok(self
+            .migrations
+            .iter()
+            .filter(|m| !applied.iter().any(|a| a.version == m.version))
+            .collect())
+    }
+
+    /// Applies every pending migration.
+    ///
+    /// - Returns: Versions applied, in order.
+    pub async fn up<D: Database>(&self, db: &D) -> Result<Vec<u64>, D::Error> {
+        self.up_to(db, u64::MAX).await
+    }
+
+    /// Applies pending migrations with a version up to `target`.
+    ///
+    /// - Returns: Versions applied, in order.
+    pub async fn up_to<D: Database>(&self, db: &D, target: u64) -> Result<Vec<u64>, D::Error> {
+        let table = identifier(&self.table)?;
+        let mut done = Vec::new();
+        for migration in self.pending(db).await? {
+            if migration.version > target {
+                break;
+            }
+            let record = format!(
+                "INSERT INTO {table} (version, name, checksum) VALUES ({}, {}, {})",
+                migration.version,
+                quote(&migration.name),
+                quote(&migration.checksum()),
+            );
+            run(db, &migration.up, &record).await?;
+            done.push(migration.version);
+        }
+        Ok(done)
+    }
+
+    /// Reverts applied migrations with a version above `target`, newest
+    /// first; `down_to(db, None)` reverts everything.
+    ///
+    /// Fails before reverting anything if one of them has no down script.
+    ///
+    /// - Returns: Versions reverted, in order.
+    pub async fn down_to<D: Database>(
+        &self,
+        db: &D,
+        target: Option<u64>,
+    ) -> Result<Vec<u64>, D::Error> {
+        let table = identifier(&self.table)?;
+        let applied = self.verified(db).await?;
+        let mut revert = Vec::new();
+        for applied in applied.iter().rev().filter(|a| Some(a.version) > target) {
+            let migration = self.find(applied.version).expect("verified");
+            let down = migration.down().ok_or_else(|| {
+                invalid(format!(
+                    "migration {} ({}) has no down script",
+                    migration.version, migration.name
+                ))
+            })?;
+            revert.push((migration.version, down));
+        }
+
+        let mut done = Vec::with_capacity(revert.len());
+        for (version, down) in revert {
+            let record = format!("DELETE FROM {table} WHERE version = {version}");
+            run(db, down, &record).await?;
+            done.push(version);
+        }
+        Ok(done)
+    }
+
+    /// Reverts the `steps` most recently applied migrations.
+    pub async fn down<D: Database>(&self, db: &D, steps: usize) -> Result<Vec<u64>, D::Error> {
+        let applied = self.applied(db).await?;
+        let target = applied
+            .len()
+            .checked_sub(steps)
+            .and_then(|n| n.checked_sub(1))
+            .map(|keep| applied[keep].version);
+        self.down_to(db, target).await
+    }
+
+    /// Applied migrations, after checking each against its source.
+    async fn verified<D: Database>(&self, db: &D) -> Result<Vec<AppliedMigration>, D::Error> {
+        let applied = self.applied(db).await?;
+        for applied in &applied {
+            let migration = self.find(applied.version).ok_or_else(|| {
+                invalid(format!(
+                    "applied migration {} ({}) is missing",
+                    applied.version, applied.name
+                ))
+            })?;
+            if migration.checksum() != applied.checksum {
+                return Err(invalid(format!(
+                    "migration {} ({}) was modified after it was applied",
+                    migration.version, migration.name
+                ))
+                .into());
+            }
+        }
+        Ok(applied)
+    }
+
+    fn find(&self, version: u64) -> Option<&Migration> {
+        self.migrations
+            .binary_search_by_key(&version, Migration::version)
+            .ok()
+            .map(|i| &self.migrations[i])
+    }
+}
+
+/// Runs a migration script and its bookkeeping statement atomically if the
+/// backend has transactions.
+async fn run<D: Database>(db: &D, script: &str, record: &str) -> Result<(), D::Error> {
+    let mut tx: TransactionHandle<D::Error> = match db.db_begin().await {
+        Ok(tx) => tx,
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            db.db_execute(script, &[]).await?;
+            db.db_execute(record, &[]).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+    tx.execute(script, &[]).await?;
+    tx.execute(record, &[]).await?;
+    tx.commit().await
+}
+
+/// Splits `0042_add_index` into its version and name.
+fn parse_stem(stem: &str) -> Option<(u64, &str)> {
+    let (version, name) = stem.split_once('_').unwrap_or((stem, ""));
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((version.parse().ok()?, name))
+}
+
+/// Quotes `text` as an SQL string literal.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+mod decode;
+mod migrate;
+mod row;
+mod statement;
+mod transaction;
+mod value;
+
+pub use self::decode::DecodeError;
+pub use self::migrate::{AppliedMigration, DEFAULT_MIGRATIONS_TABLE, Migration, Migrator};
+pub use self::row::{ResultSet, Row};
+pub use self::statement::Statement;
+pub use self::transaction::{Transaction, TransactionHandle};
+pub use self::value::{DbValue, FromDbValue};
+
+use crate::{Backend, Unsupported};
+
+/// Database access.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait Database: Backend {
+    /// Executes a database query that returns rows.
+    ///
+    /// - `query`: SQL query string.
+    /// - `params`: Positional parameters bound to the query's placeholders.
+    /// - Returns: Column names and rows.
+    async fn db_query(&self, query: &str, params: &[DbValue]) -> Result<ResultSet, Self::Error> {
+        Err(Unsupported::new("db_query").into())
+    }
+
+    /// Executes a database command (e.g., INSERT/UPDATE/DELETE).
+    ///
+    /// - `query`: SQL statement.
+    /// - `params`: Positional parameters bound to the statement's placeholders.
+    /// - Returns: Number of affected rows.
+    async fn db_execute(&self, query: &str, params: &[DbValue]) -> Result<u64, Self::Error> {
+        Err(Unsupported::new("db_execute").into())
+    }
+
+    /// Executes a query and decodes every row into `T` with serde.
+    ///
+    /// See [`Row::decode`] for how columns map onto `T`. Decoding failures
+    /// surface as an `InvalidData` I/O error, i.e.
+    /// [`ErrorKind::InvalidInput`](crate::ErrorKind::InvalidInput).
+    ///
+    /// Defaults to [`db_query`](Self::db_query) followed by
+    /// [`ResultSet::decode`].
+    async fn db_query_as<T>(&self, query: &str, params: &[DbValue]) -> Result<Vec<T>, Self::Error>
+    where
+        Self: Sized,
+        T: DeserializeOwned + Send,
+    {
+        let rows = self.db_query(query, params).await?;
+        Ok(rows.decode().map_err(std::io::Error::from)?)
+    }
+
+    /// Starts a transaction.
+    ///
+    /// - Returns: A handle owning one connection until it is committed,
+    ///   rolled back or dropped.
+    async fn db_begin(&self) -> Result<TransactionHandle<Self::Error>, Self::Error> {
+        Err(Unsupported::new("db_begin").into())
+    }
+
+    /// Prepares a statement ahead of use.
+    ///
+    /// Backends with a statement cache compile `query` once, report syntax
+    /// errors here, and reuse the compiled form whenever the returned
+    /// [`Statement`] (or the same SQL text) is executed again.
+    ///
+    /// Defaults to wrapping `query` without inspecting it.
+    async fn db_prepare(&self, query: &str) -> Result<Statement, Self::Error> {
+        Ok(Statement::new(query))
+    }
+}
+
+/// Checks that `name` can be spliced into SQL as a bare identifier.
+fn identifier(name: &str) -> std::io::Result<&str> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(name)
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("`{name}` is not a valid SQL identifier"),
+        ))
+    }
+}
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+
+use super::decode::RowDeserializer;
+use super::{DbValue, DecodeError, FromDbValue};
+
+/// One row of a [`ResultSet`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    columns: Arc<[String]>,
+    values: Vec<DbValue>,
+}
+
+impl Row {
+    /// Column names, shared with the rest of the result set.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Values in column order.
+    pub fn values(&self) -> &[DbValue] {
+        &self.values
+    }
+
+    /// Number of columns.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Position of the column called `name`.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == name)
+    }
+
+    /// Raw value of the column called `name`.
+    pub fn value(&self, name: &str) -> Option<&DbValue> {
+        self.index_of(name).map(|i| &self.values[i])
+    }
+
+    /// Converts the column called `name` to `T`.
+    ///
+    /// - Returns: An error if there is no such column or its value does not
+    ///   convert; use `Option<T>` for nullable columns.
+    pub fn get<T: FromDbValue>(&self, name: &str) -> Result<T, DecodeError> {
+        let value = self
+            .value(name)
+            .ok_or_else(|| DecodeError::new(format!("no column named `{name}`")))?;
+        T::from_db_value(value).map_err(|err| err.in_column(name))
+    }
+
+    /// Converts the column at `index` to `T`.
+    pub fn get_at<T: FromDbValue>(&self, index: usize) -> Result<T, DecodeError> {
+        let value = self
+            .values
+            .get(index)
+            .ok_or_else(|| DecodeError::new(format!("no column at index {index}")))?;
+        T::from_db_value(value).map_err(|err| err.in_column(&self.columns[index]))
+    }
+
+    /// Decodes the row with serde.
+    ///
+    /// Structs and maps are filled by column name, tuples and sequences by
+    /// position, and a single-column row also decodes straight into a scalar
+    /// such as `i64` or `String`.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, DecodeError> {
+        T::deserialize(RowDeserializer::new(self))
+    }
+
+    /// Consumes the row, returning its values.
+    pub fn into_values(self) -> Vec<DbValue> {
+        self.values
+    }
+}
+
+/// Rows returned by [`Database::db_query`](super::Database::db_query).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultSet {
+    columns: Arc<[String]>,
+    rows: Vec<Row>,
+}
+
+impl ResultSet {
+    /// Creates an empty result set with the given column names.
+    pub fn new<I, S>(columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ResultSet {
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends a row.
+    ///
+    /// # Panics
+    ///
+    /// If `values` does not have one entry per column.
+    pub fn push(&mut self, values: Vec<DbValue>) {
+        assert_eq!(
+            values.len(),
+            self.columns.len(),
+            "row has {} values for {} columns",
+            values.len(),
+            self.columns.len()
+        );
+        self.rows.push(Row {
+            columns: self.columns.clone(),
+            values,
+        });
+    }
+
+    /// Column names.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Rows in the order the database returned them.
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Iterates over the rows.
+    pub fn iter(&self) -> std::slice::Iter<'_, Row> {
+        self.rows.iter()
+    }
+
+    /// Decodes every row with [`Row::decode`].
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<Vec<T>, DecodeError> {
+        self.rows.iter().map(Row::decode).collect()
+    }
+}
+
+impl IntoIterator for ResultSet {
+    type Item = Row;
+    type IntoIter = std::vec::IntoIter<Row>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ResultSet {
+    type Item = &'a Row;
+    type IntoIter = std::slice::Iter<'a, Row>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
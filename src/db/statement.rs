@@ -0,0 +1,77 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A statement prepared by [`Database::db_prepare`](super::Database::db_prepare).
+///
+/// Cheap to clone. It dereferences to its SQL text, so it can be passed
+/// wherever a query string is expected; backends with a statement cache
+/// then reuse the compiled form instead of parsing the SQL again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Statement {
+    sql: Arc<str>,
+    params: Option<usize>,
+    columns: Option<Arc<[String]>>,
+}
+
+impl Statement {
+    /// Wraps SQL text without inspecting it.
+    pub fn new(sql: impl Into<Arc<str>>) -> Self {
+        Statement {
+            sql: sql.into(),
+            params: None,
+            columns: None,
+        }
+    }
+
+    /// Records the number of parameters the statement expects.
+    pub fn with_params(mut self, params: usize) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Records the names of the columns the statement returns.
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// SQL text of the statement.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Number of parameters, if the backend reported it.
+    pub fn params(&self) -> Option<usize> {
+        self.params
+    }
+
+    /// Result column names, if the backend reported them.
+    pub fn columns(&self) -> Option<&[String]> {
+        self.columns.as_deref()
+    }
+}
+
+impl Deref for Statement {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl AsRef<str> for Statement {
+    fn as_ref(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.sql)
+    }
+}
@@ -0,0 +1,65 @@
+use std::io;
+
+use async_trait::async_trait;
+
+use super::{DbValue, ResultSet, identifier};
+
+/// An open transaction returned by [`Database::db_begin`](super::Database::db_begin).
+///
+/// Statements run on the transaction's own connection and become visible to
+/// others only on [`commit`](Self::commit). A handle dropped without
+/// committing is rolled back.
+#[async_trait]
+pub trait Transaction: Send {
+    /// Error type of the transaction; the backend's error type.
+    type Error: From<io::Error>;
+
+    /// Executes a query inside the transaction.
+    ///
+    /// See [`Database::db_query`](super::Database::db_query).
+    async fn query(&mut self, query: &str, params: &[DbValue]) -> Result<ResultSet, Self::Error>;
+
+    /// Executes a statement inside the transaction.
+    ///
+    /// See [`Database::db_execute`](super::Database::db_execute).
+    async fn execute(&mut self, query: &str, params: &[DbValue]) -> Result<u64, Self::Error>;
+
+    /// Makes every change of the transaction permanent.
+    async fn commit(self: Box<Self>) -> Result<(), Self::Error>;
+
+    /// Discards every change of the transaction.
+    async fn rollback(self: Box<Self>) -> Result<(), Self::Error>;
+
+    /// Marks a point that [`rollback_to`](Self::rollback_to) can return to.
+    ///
+    /// - `name`: Plain SQL identifier (letters, digits and `_`).
+    ///
+    /// Defaults to executing `SAVEPOINT name`.
+    async fn savepoint(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.execute(&format!("SAVEPOINT {}", identifier(name)?), &[])
+            .await
+            .map(drop)
+    }
+
+    /// Undoes the changes made since `savepoint(name)`; the savepoint stays
+    /// active.
+    ///
+    /// Defaults to executing `ROLLBACK TO SAVEPOINT name`.
+    async fn rollback_to(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.execute(&format!("ROLLBACK TO SAVEPOINT {}", identifier(name)?), &[])
+            .await
+            .map(drop)
+    }
+
+    /// Forgets a savepoint, keeping its changes in the transaction.
+    ///
+    /// Defaults to executing `RELEASE SAVEPOINT name`.
+    async fn release(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.execute(&format!("RELEASE SAVEPOINT {}", identifier(name)?), &[])
+            .await
+            .map(drop)
+    }
+}
+
+/// Boxed transaction returned by [`Database::db_begin`](super::Database::db_begin).
+pub type TransactionHandle<E> = Box<dyn Transaction<Error = E>>;
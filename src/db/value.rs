@@ -0,0 +1,246 @@
+use std::time::SystemTime;
+
+use super::DecodeError;
+
+/// A single SQL value, used both for statement parameters and for the
+/// columns of a [`Row`](super::Row).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbValue {
+    /// SQL `NULL`.
+    Null,
+    /// Boolean.
+    Bool(bool),
+    /// Signed 64-bit integer.
+    Int(i64),
+    /// 64-bit floating point number.
+    Float(f64),
+    /// UTF-8 text.
+    Text(String),
+    /// Binary blob.
+    Bytes(Vec<u8>),
+    /// Point in time.
+    Timestamp(SystemTime),
+}
+
+impl DbValue {
+    /// Returns `true` for [`DbValue::Null`].
+    pub fn is_null(&self) -> bool {
+        matches!(self, DbValue::Null)
+    }
+
+    /// Name of the variant, for error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DbValue::Null => "null",
+            DbValue::Bool(_) => "bool",
+            DbValue::Int(_) => "int",
+            DbValue::Float(_) => "float",
+            DbValue::Text(_) => "text",
+            DbValue::Bytes(_) => "bytes",
+            DbValue::Timestamp(_) => "timestamp",
+        }
+    }
+
+    /// Returns the boolean, also accepting the integers `0` and `1`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            DbValue::Bool(v) => Some(v),
+            DbValue::Int(0) => Some(false),
+            DbValue::Int(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            DbValue::Int(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the float value, also accepting integers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            DbValue::Float(v) => Some(v),
+            DbValue::Int(v) => Some(v as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the text value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DbValue::Text(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the blob value, also accepting text as its UTF-8 bytes.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            DbValue::Bytes(v) => Some(v),
+            DbValue::Text(v) => Some(v.as_bytes()),
+            _ => None,
+        }
+    }
+
+    /// Returns the timestamp value.
+    pub fn as_timestamp(&self) -> Option<SystemTime> {
+        match *self {
+            DbValue::Timestamp(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for DbValue {
+    fn from(v: bool) -> Self {
+        DbValue::Bool(v)
+    }
+}
+
+macro_rules! from_int {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for DbValue {
+            fn from(v: $ty) -> Self {
+                DbValue::Int(v.into())
+            }
+        }
+    )*};
+}
+
+from_int!(i8, i16, i32, i64, u8, u16, u32);
+
+impl From<f32> for DbValue {
+    fn from(v: f32) -> Self {
+        DbValue::Float(v.into())
+    }
+}
+
+impl From<f64> for DbValue {
+    fn from(v: f64) -> Self {
+        DbValue::Float(v)
+    }
+}
+
+impl From<&str> for DbValue {
+    fn from(v: &str) -> Self {
+        DbValue::Text(v.to_owned())
+    }
+}
+
+impl From<String> for DbValue {
+    fn from(v: String) -> Self {
+        DbValue::Text(v)
+    }
+}
+
+impl From<&[u8]> for DbValue {
+    fn from(v: &[u8]) -> Self {
+        DbValue::Bytes(v.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for DbValue {
+    fn from(v: Vec<u8>) -> Self {
+        DbValue::Bytes(v)
+    }
+}
+
+impl From<SystemTime> for DbValue {
+    fn from(v: SystemTime) -> Self {
+        DbValue::Timestamp(v)
+    }
+}
+
+impl<T: Into<DbValue>> From<Option<T>> for DbValue {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(DbValue::Null, Into::into)
+    }
+}
+
+/// Conversion out of a [`DbValue`], used by the typed
+/// [`Row`](super::Row) getters.
+pub trait FromDbValue: Sized {
+    /// Converts `value`, failing if it has an incompatible type.
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError>;
+}
+
+fn mismatch(expected: &str, value: &DbValue) -> DecodeError {
+    DecodeError::new(format!("expected {expected}, found {}", value.type_name()))
+}
+
+impl FromDbValue for DbValue {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromDbValue for bool {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        value.as_bool().ok_or_else(|| mismatch("bool", value))
+    }
+}
+
+macro_rules! from_db_int {
+    ($($ty:ty),*) => {$(
+        impl FromDbValue for $ty {
+            fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+                let v = value.as_i64().ok_or_else(|| mismatch("int", value))?;
+                <$ty>::try_from(v).map_err(|_| {
+                    DecodeError::new(format!("{v} is out of range for {}", stringify!($ty)))
+                })
+            }
+        }
+    )*};
+}
+
+from_db_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl FromDbValue for f64 {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        value.as_f64().ok_or_else(|| mismatch("float", value))
+    }
+}
+
+impl FromDbValue for f32 {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        f64::from_db_value(value).map(|v| v as f32)
+    }
+}
+
+impl FromDbValue for String {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        value
+            .as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| mismatch("text", value))
+    }
+}
+
+impl FromDbValue for Vec<u8> {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        value
+            .as_bytes()
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| mismatch("bytes", value))
+    }
+}
+
+impl FromDbValue for SystemTime {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        value
+            .as_timestamp()
+            .ok_or_else(|| mismatch("timestamp", value))
+    }
+}
+
+impl<T: FromDbValue> FromDbValue for Option<T> {
+    fn from_db_value(value: &DbValue) -> Result<Self, DecodeError> {
+        match value {
+            DbValue::Null => Ok(None),
+            value => T::from_db_value(value).map(Some),
+        }
+    }
+}
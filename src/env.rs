@@ -0,0 +1,17 @@
+use crate::{Backend, Unsupported};
+
+/// Environment / configuration.
+#[allow(unused_variables)]
+pub trait Env: Backend {
+    /// Retrieves the value of an environment variable.
+    ///
+    /// Defaults to `None`.
+    fn get_env(&self, key: &str) -> Option<String> {
+        None
+    }
+
+    /// Sets an environment variable.
+    fn set_env(&self, key: &str, value: &str) -> Result<(), Self::Error> {
+        Err(Unsupported::new("set_env").into())
+    }
+}
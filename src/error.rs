@@ -0,0 +1,289 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+
+use crate::Unsupported;
+use crate::process::Denied;
+
+/// Category of a failed operation, independent of the backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The file, key, row or resource does not exist.
+    NotFound,
+    /// The target already exists.
+    AlreadyExists,
+    /// The caller lacks permission for the operation.
+    PermissionDenied,
+    /// The operation did not complete in time.
+    Timeout,
+    /// The remote end refused the connection.
+    ConnectionRefused,
+    /// The backend does not implement the operation.
+    Unsupported,
+    /// An argument was malformed or out of range.
+    InvalidInput,
+    /// The operation conflicts with the current state (e.g. a concurrent
+    /// modification or constraint violation).
+    Conflict,
+    /// A policy refused the operation before it ran (e.g. a command outside
+    /// a [`Sandboxed`](crate::process::Sandboxed) allowlist).
+    Denied,
+    /// Any other backend-specific failure.
+    Backend,
+}
+
+/// Accessor for the [`ErrorKind`] of an error.
+///
+/// Required of every [`Backend::Error`](crate::Backend::Error) so generic
+/// code can tell failures apart without knowing the backend. Custom error
+/// types implement it by mapping their variants onto a kind.
+pub trait HasErrorKind {
+    /// Returns the category of this error.
+    fn kind(&self) -> ErrorKind;
+}
+
+/// Standard error type shipped with `extio`.
+///
+/// Backends that have no reason to define their own error type can use this
+/// one; it converts from `std::io::Error`, `http::Error` and, with the
+/// matching features, `reqwest::Error`, `tungstenite::Error` and
+/// `rusqlite::Error`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ExtioError {
+    /// See [`ErrorKind::NotFound`].
+    NotFound(String),
+    /// See [`ErrorKind::AlreadyExists`].
+    AlreadyExists(String),
+    /// See [`ErrorKind::PermissionDenied`].
+    PermissionDenied(String),
+    /// See [`ErrorKind::Timeout`].
+    Timeout(String),
+    /// See [`ErrorKind::ConnectionRefused`].
+    ConnectionRefused(String),
+    /// See [`ErrorKind::Unsupported`].
+    Unsupported(Unsupported),
+    /// See [`ErrorKind::InvalidInput`].
+    InvalidInput(String),
+    /// See [`ErrorKind::Conflict`].
+    Conflict(String),
+    /// See [`ErrorKind::Denied`].
+    Denied(Denied),
+    /// See [`ErrorKind::Backend`]. Keeps the underlying error as its source.
+    Backend(Box<dyn StdError + Send + Sync>),
+}
+
+impl ExtioError {
+    /// Wraps an arbitrary error as [`ExtioError::Backend`].
+    pub fn backend<E>(err: E) -> Self
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        ExtioError::Backend(err.into())
+    }
+}
+
+impl HasErrorKind for ExtioError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ExtioError::NotFound(_) => ErrorKind::NotFound,
+            ExtioError::AlreadyExists(_) => ErrorKind::AlreadyExists,
+            ExtioError::PermissionDenied(_) => ErrorKind::PermissionDenied,
+            ExtioError::Timeout(_) => ErrorKind::Timeout,
+            ExtioError::ConnectionRefused(_) => ErrorKind::ConnectionRefused,
+            ExtioError::Unsupported(_) => ErrorKind::Unsupported,
+            ExtioError::InvalidInput(_) => ErrorKind::InvalidInput,
+            ExtioError::Conflict(_) => ErrorKind::Conflict,
+            ExtioError::Denied(_) => ErrorKind::Denied,
+            ExtioError::Backend(_) => ErrorKind::Backend,
+        }
+    }
+}
+
+impl fmt::Display for ExtioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtioError::NotFound(msg) => write!(f, "not found: {msg}"),
+            ExtioError::AlreadyExists(msg) => write!(f, "already exists: {msg}"),
+            ExtioError::PermissionDenied(msg) => write!(f, "permission denied: {msg}"),
+            ExtioError::Timeout(msg) => write!(f, "timed out: {msg}"),
+            ExtioError::ConnectionRefused(msg) => write!(f, "connection refused: {msg}"),
+            ExtioError::Unsupported(err) => err.fmt(f),
+            ExtioError::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            ExtioError::Conflict(msg) => write!(f, "conflict: {msg}"),
+            ExtioError::Denied(err) => err.fmt(f),
+            ExtioError::Backend(err) => err.fmt(f),
+        }
+    }
+}
+
+impl StdError for ExtioError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ExtioError::Unsupported(err) => Some(err),
+            ExtioError::Denied(err) => Some(err),
+            ExtioError::Backend(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl HasErrorKind for Unsupported {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Unsupported
+    }
+}
+
+impl From<Unsupported> for ExtioError {
+    fn from(err: Unsupported) -> Self {
+        ExtioError::Unsupported(err)
+    }
+}
+
+impl HasErrorKind for Denied {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Denied
+    }
+}
+
+impl From<Denied> for ExtioError {
+    fn from(err: Denied) -> Self {
+        ExtioError::Denied(err)
+    }
+}
+
+impl HasErrorKind for io::Error {
+    fn kind(&self) -> ErrorKind {
+        match io::Error::kind(self) {
+            io::ErrorKind::NotFound => ErrorKind::NotFound,
+            io::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+            io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            io::ErrorKind::TimedOut => ErrorKind::Timeout,
+            io::ErrorKind::ConnectionRefused => ErrorKind::ConnectionRefused,
+            io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+            io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => ErrorKind::InvalidInput,
+            _ => ErrorKind::Backend,
+        }
+    }
+}
+
+impl From<io::Error> for ExtioError {
+    fn from(err: io::Error) -> Self {
+        let msg = err.to_string();
+        match HasErrorKind::kind(&err) {
+            ErrorKind::NotFound => ExtioError::NotFound(msg),
+            ErrorKind::AlreadyExists => ExtioError::AlreadyExists(msg),
+            ErrorKind::PermissionDenied => ExtioError::PermissionDenied(msg),
+            ErrorKind::Timeout => ExtioError::Timeout(msg),
+            ErrorKind::ConnectionRefused => ExtioError::ConnectionRefused(msg),
+            ErrorKind::Unsupported => {
+                ExtioError::Unsupported(Unsupported::new("io").with_message(msg))
+            }
+            ErrorKind::InvalidInput => ExtioError::InvalidInput(msg),
+            _ => ExtioError::Backend(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for ExtioError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            return ExtioError::Timeout(err.to_string());
+        }
+        if err.is_builder() {
+            return ExtioError::InvalidInput(err.to_string());
+        }
+        if let Some(status) = err.status() {
+            let msg = err.to_string();
+            match status.as_u16() {
+                401 | 403 => return ExtioError::PermissionDenied(msg),
+                404 => return ExtioError::NotFound(msg),
+                409 | 412 => return ExtioError::Conflict(msg),
+                _ => {}
+            }
+        }
+        if err.is_connect() && io_source_kind(&err) == Some(io::ErrorKind::ConnectionRefused) {
+            return ExtioError::ConnectionRefused(err.to_string());
+        }
+        ExtioError::Backend(Box::new(err))
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl From<tokio_tungstenite::tungstenite::Error> for ExtioError {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        use tokio_tungstenite::tungstenite::Error;
+
+        match err {
+            Error::Io(err) => err.into(),
+            Error::Url(_) | Error::HttpFormat(_) => ExtioError::InvalidInput(err.to_string()),
+            Error::Http(ref resp) => {
+                let msg = err.to_string();
+                match resp.status().as_u16() {
+                    401 | 403 => ExtioError::PermissionDenied(msg),
+                    404 => ExtioError::NotFound(msg),
+                    _ => ExtioError::Backend(Box::new(err)),
+                }
+            }
+            err => ExtioError::Backend(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for ExtioError {
+    fn from(err: rusqlite::Error) -> Self {
+        use rusqlite::Error;
+        use rusqlite::ffi::ErrorCode;
+
+        let msg = err.to_string();
+        match err {
+            Error::QueryReturnedNoRows => ExtioError::NotFound(msg),
+            Error::SqliteFailure(ref code, _) => match code.code {
+                ErrorCode::ConstraintViolation => ExtioError::Conflict(msg),
+                ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked => ExtioError::Timeout(msg),
+                ErrorCode::PermissionDenied | ErrorCode::ReadOnly => {
+                    ExtioError::PermissionDenied(msg)
+                }
+                ErrorCode::CannotOpen => ExtioError::NotFound(msg),
+                ErrorCode::TypeMismatch | ErrorCode::TooBig => ExtioError::InvalidInput(msg),
+                _ => ExtioError::Backend(Box::new(err)),
+            },
+            Error::SqlInputError { .. }
+            | Error::InvalidParameterCount(..)
+            | Error::InvalidParameterName(_)
+            | Error::InvalidColumnIndex(_)
+            | Error::InvalidColumnName(_)
+            | Error::InvalidColumnType(..)
+            | Error::IntegralValueOutOfRange(..)
+            | Error::FromSqlConversionFailure(..)
+            | Error::ToSqlConversionFailure(_)
+            | Error::ExecuteReturnedResults
+            | Error::MultipleStatement
+            | Error::NulError(_)
+            | Error::InvalidPath(_) => ExtioError::InvalidInput(msg),
+            err => ExtioError::Backend(Box::new(err)),
+        }
+    }
+}
+
+impl From<http::Error> for ExtioError {
+    fn from(err: http::Error) -> Self {
+        ExtioError::InvalidInput(err.to_string())
+    }
+}
+
+/// Finds the first `io::Error` in the source chain of `err`.
+#[cfg(feature = "reqwest")]
+fn io_source_kind(err: &(dyn StdError + 'static)) -> Option<io::ErrorKind> {
+    let mut source = err.source();
+    while let Some(err) = source {
+        if let Some(io) = err.downcast_ref::<io::Error>() {
+            return Some(io.kind());
+        }
+        source = err.source();
+    }
+    None
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Kind of a filesystem entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// Regular file.
+    File,
+    /// Directory.
+    Dir,
+    /// Symbolic link (not followed).
+    Symlink,
+    /// Anything else (socket, FIFO, device, ...).
+    Other,
+}
+
+impl From<std::fs::FileType> for FileKind {
+    fn from(ft: std::fs::FileType) -> Self {
+        if ft.is_symlink() {
+            FileKind::Symlink
+        } else if ft.is_dir() {
+            FileKind::Dir
+        } else if ft.is_file() {
+            FileKind::File
+        } else {
+            FileKind::Other
+        }
+    }
+}
+
+/// Access permissions of a filesystem entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Permissions {
+    /// Whether the entry is read-only.
+    pub readonly: bool,
+    /// Unix permission bits, if the backend has them.
+    pub mode: Option<u32>,
+}
+
+impl From<std::fs::Permissions> for Permissions {
+    fn from(perms: std::fs::Permissions) -> Self {
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            Some(perms.mode() & 0o7777)
+        };
+        #[cfg(not(unix))]
+        let mode = None;
+
+        Permissions {
+            readonly: perms.readonly(),
+            mode,
+        }
+    }
+}
+
+/// Metadata of a filesystem entry, returned by
+/// [`FileIo::stat`](crate::FileIo::stat).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    /// Kind of the entry.
+    pub kind: FileKind,
+    /// Size in bytes; `0` for directories on backends that do not track it.
+    pub size: u64,
+    /// Last modification time, if known.
+    pub modified: Option<SystemTime>,
+    /// Access permissions.
+    pub permissions: Permissions,
+}
+
+impl From<std::fs::Metadata> for Metadata {
+    fn from(meta: std::fs::Metadata) -> Self {
+        Metadata {
+            kind: meta.file_type().into(),
+            size: meta.len(),
+            modified: meta.modified().ok(),
+            permissions: meta.permissions().into(),
+        }
+    }
+}
+
+/// An entry returned by [`FileIo::list_dir`](crate::FileIo::list_dir) and
+/// [`FileIo::walk_dir`](crate::FileIo::walk_dir).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    /// File name of the entry, without its directory.
+    pub name: String,
+    /// Full path of the entry (the listed directory joined with `name`).
+    pub path: PathBuf,
+    /// Kind of the entry.
+    pub kind: FileKind,
+    /// Size in bytes; `0` for directories on backends that do not track it.
+    pub size: u64,
+    /// Last modification time, if known.
+    pub modified: Option<SystemTime>,
+    /// Access permissions.
+    pub permissions: Permissions,
+}
+
+impl DirEntry {
+    /// Builds an entry for `path` from its metadata.
+    ///
+    /// The name is the last component of `path`.
+    pub fn new(path: impl Into<PathBuf>, meta: Metadata) -> Self {
+        let path = path.into();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        DirEntry {
+            name,
+            path,
+            kind: meta.kind,
+            size: meta.size,
+            modified: meta.modified,
+            permissions: meta.permissions,
+        }
+    }
+
+    /// Returns the metadata part of the entry.
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            kind: self.kind,
+            size: self.size,
+            modified: self.modified,
+            permissions: self.permissions,
+        }
+    }
+
+    /// Returns `true` if the entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.kind == FileKind::Dir
+    }
+
+    /// Returns `true` if the entry is a regular file.
+    pub fn is_file(&self) -> bool {
+        self.kind == FileKind::File
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+
+mod entry;
+mod options;
+mod walk;
+
+pub use self::entry::{DirEntry, FileKind, Metadata, Permissions};
+pub use self::options::WriteOptions;
+pub use self::walk::{WalkOptions, WalkStream};
+
+use crate::stream::{ByteReader, read_all, reader_from_bytes};
+use crate::{Backend, ErrorKind, HasErrorKind, Unsupported};
+
+/// File and directory operations.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait FileIo: Backend {
+    /// Reads the entire contents of a file from disk into memory.
+    ///
+    /// - `path`: Path to the file.
+    /// - Returns: File contents as `Vec<u8>`.
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>, Self::Error> {
+        Err(Unsupported::new("read_file").into())
+    }
+
+    /// Writes binary data into a file, creating or truncating it.
+    ///
+    /// - `path`: Path where the file will be written.
+    /// - `data`: Raw bytes to write.
+    ///
+    /// Not atomic: a crash mid-write can leave a partial file. Use
+    /// [`atomic_write_file`](Self::atomic_write_file) where that matters.
+    async fn write_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
+        Err(Unsupported::new("write_file").into())
+    }
+
+    /// Deletes a file from disk.
+    ///
+    /// - `path`: Path to the file.
+    async fn delete_file(&self, path: &Path) -> Result<(), Self::Error> {
+        Err(Unsupported::new("delete_file").into())
+    }
+
+    /// Writes binary data into a file with explicit [`WriteOptions`].
+    ///
+    /// - `path`: Path where the file will be written.
+    /// - `data`: Raw bytes to write.
+    /// - `options`: Atomicity and overwrite behaviour.
+    ///
+    /// Defaults to [`write_file`](Self::write_file) for the default options
+    /// and [`Unsupported`] otherwise, since atomic and no-overwrite
+    /// semantics cannot be emulated safely on top of the other methods.
+    async fn write_file_with(
+        &self,
+        path: &Path,
+        data: &[u8],
+        options: WriteOptions,
+    ) -> Result<(), Self::Error> {
+        if options == WriteOptions::default() {
+            self.write_file(path, data).await
+        } else {
+            Err(Unsupported::new("write_file_with").into())
+        }
+    }
+
+    /// Atomically replaces a file's contents.
+    ///
+    /// Shorthand for [`write_file_with`](Self::write_file_with) with
+    /// [`WriteOptions::atomic`] set.
+    async fn atomic_write_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
+        self.write_file_with(path, data, WriteOptions::new().atomic(true))
+            .await
+    }
+
+    /// Appends binary data to a file, creating it if it does not exist.
+    ///
+    /// - `path`: Path to the file.
+    /// - `data`: Raw bytes to append.
+    async fn append_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
+        Err(Unsupported::new("append_file").into())
+    }
+
+    /// Returns metadata for a file or directory, following symlinks.
+    ///
+    /// - `path`: Path to the entry.
+    async fn stat(&self, path: &Path) -> Result<Metadata, Self::Error> {
+        Err(Unsupported::new("stat").into())
+    }
+
+    /// Checks whether a file or directory exists.
+    ///
+    /// Defaults to [`stat`](Self::stat), treating [`ErrorKind::NotFound`] as
+    /// `false`.
+    async fn exists(&self, path: &Path) -> Result<bool, Self::Error> {
+        match self.stat(path).await {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Renames or moves a file or directory, replacing `to` if it is a file.
+    ///
+    /// - `from`: Current path.
+    /// - `to`: New path.
+    async fn rename(&self, from: &Path, to: &Path) -> Result<(), Self::Error> {
+        Err(Unsupported::new("rename").into())
+    }
+
+    /// Copies a file, creating or truncating the destination.
+    ///
+    /// - `from`: Source file.
+    /// - `to`: Destination file.
+    /// - Returns: Number of bytes copied.
+    ///
+    /// Defaults to [`read_file`](Self::read_file) followed by
+    /// [`write_file`](Self::write_file).
+    async fn copy(&self, from: &Path, to: &Path) -> Result<u64, Self::Error> {
+        let data = self.read_file(from).await?;
+        self.write_file(to, &data).await?;
+        Ok(data.len() as u64)
+    }
+
+    /// Creates a directory and any missing parents.
+    ///
+    /// - `path`: Directory path. Succeeds if it already exists.
+    async fn create_dir_all(&self, path: &Path) -> Result<(), Self::Error> {
+        Err(Unsupported::new("create_dir_all").into())
+    }
+
+    /// Removes a directory and everything below it.
+    ///
+    /// - `path`: Directory path.
+    async fn remove_dir_all(&self, path: &Path) -> Result<(), Self::Error> {
+        Err(Unsupported::new("remove_dir_all").into())
+    }
+
+    /// Changes the permissions of a file or directory.
+    ///
+    /// - `path`: Path to the entry.
+    /// - `permissions`: New permissions. `mode` takes precedence over
+    ///   `readonly` on backends that support Unix permission bits.
+    async fn set_permissions(
+        &self,
+        path: &Path,
+        permissions: Permissions,
+    ) -> Result<(), Self::Error> {
+        Err(Unsupported::new("set_permissions").into())
+    }
+
+    /// Lists the contents of a directory.
+    ///
+    /// - `path`: Directory path.
+    /// - Returns: One [`DirEntry`] per file/directory, with its metadata.
+    async fn list_dir(&self, path: &Path) -> Result<Vec<DirEntry>, Self::Error> {
+        Err(Unsupported::new("list_dir").into())
+    }
+
+    /// Recursively walks a directory tree, breadth-first.
+    ///
+    /// - `path`: Root directory; it is not yielded itself.
+    /// - `options`: Depth limit and glob filter.
+    /// - Returns: A stream of entries below `path`.
+    ///
+    /// Defaults to repeated [`list_dir`](Self::list_dir) calls. Symlinks are
+    /// yielded but not followed.
+    fn walk_dir<'a>(&'a self, path: &Path, options: WalkOptions) -> WalkStream<'a, Self::Error> {
+        walk::walk(self, path, options)
+    }
+
+    /// Opens a file for streaming reads.
+    ///
+    /// Defaults to reading the whole file with [`read_file`](Self::read_file).
+    async fn read_file_stream(&self, path: &Path) -> Result<ByteReader, Self::Error> {
+        let data = self.read_file(path).await?;
+        Ok(reader_from_bytes(data))
+    }
+
+    /// Writes a file from an asynchronous reader, creating or truncating it.
+    ///
+    /// - Returns: Number of bytes written.
+    ///
+    /// Defaults to reading `reader` into memory and calling
+    /// [`write_file`](Self::write_file).
+    async fn write_file_stream(&self, path: &Path, reader: ByteReader) -> Result<u64, Self::Error> {
+        let data = read_all(reader).await?;
+        self.write_file(path, &data).await?;
+        Ok(data.len() as u64)
+    }
+}
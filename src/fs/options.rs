@@ -0,0 +1,33 @@
+/// Options for [`FileIo::write_file_with`](crate::FileIo::write_file_with).
+///
+/// The default (`WriteOptions::new()`) behaves like
+/// [`FileIo::write_file`](crate::FileIo::write_file): create or truncate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WriteOptions {
+    /// Write to a temporary sibling, fsync it, rename it over the target and
+    /// fsync the directory, so readers see either the old or the new
+    /// contents and a crash never leaves a half-written file.
+    pub atomic: bool,
+    /// Fail with [`ErrorKind::AlreadyExists`](crate::ErrorKind::AlreadyExists)
+    /// instead of replacing an existing file.
+    pub create_new: bool,
+}
+
+impl WriteOptions {
+    /// Create-or-truncate, non-atomic.
+    pub fn new() -> Self {
+        WriteOptions::default()
+    }
+
+    /// Sets [`atomic`](Self::atomic).
+    pub fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Sets [`create_new`](Self::create_new).
+    pub fn create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
+}
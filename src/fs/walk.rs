@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use futures_core::Stream;
+use glob::{MatchOptions, Pattern};
+
+use crate::ExtioError;
+use crate::fs::{DirEntry, FileIo};
+
+/// Stream of entries returned by [`FileIo::walk_dir`].
+pub type WalkStream<'a, E> = Pin<Box<dyn Stream<Item = Result<DirEntry, E>> + Send + 'a>>;
+
+/// Options for [`FileIo::walk_dir`].
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    max_depth: Option<usize>,
+    pattern: Option<Pattern>,
+}
+
+impl WalkOptions {
+    /// Walks the whole tree and yields every entry.
+    pub fn new() -> Self {
+        WalkOptions::default()
+    }
+
+    /// Stops descending below `depth`. Direct children of the root are at
+    /// depth 1, so `max_depth(1)` behaves like `list_dir` and `max_depth(0)`
+    /// yields nothing.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Only yields entries whose path relative to the root matches the glob
+    /// `pattern` (e.g. `"**/*.rs"`). `*` does not cross `/`; `**` does.
+    ///
+    /// Non-matching directories are still descended into.
+    pub fn glob(mut self, pattern: &str) -> Result<Self, ExtioError> {
+        let pattern = Pattern::new(pattern)
+            .map_err(|err| ExtioError::InvalidInput(format!("glob `{pattern}`: {err}")))?;
+        self.pattern = Some(pattern);
+        Ok(self)
+    }
+
+    fn matches(&self, root: &Path, path: &Path) -> bool {
+        let Some(pattern) = &self.pattern else {
+            return true;
+        };
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        pattern.matches_path_with(
+            relative,
+            MatchOptions {
+                case_sensitive: true,
+                require_literal_separator: true,
+                require_literal_leading_dot: false,
+            },
+        )
+    }
+}
+
+struct WalkState<'a, B: ?Sized> {
+    backend: &'a B,
+    root: PathBuf,
+    options: WalkOptions,
+    /// Directories still to be listed, with their depth.
+    dirs: VecDeque<(PathBuf, usize)>,
+    /// Entries listed but not yet yielded, with their depth.
+    pending: VecDeque<(DirEntry, usize)>,
+}
+
+/// Default [`FileIo::walk_dir`] implementation on top of `list_dir`.
+pub(crate) fn walk<'a, B>(
+    backend: &'a B,
+    root: &Path,
+    options: WalkOptions,
+) -> WalkStream<'a, B::Error>
+where
+    B: FileIo + ?Sized,
+{
+    let mut dirs = VecDeque::new();
+    if options.max_depth != Some(0) {
+        dirs.push_back((root.to_path_buf(), 0));
+    }
+    let state = WalkState {
+        backend,
+        root: root.to_path_buf(),
+        options,
+        dirs,
+        pending: VecDeque::new(),
+    };
+
+    Box::pin(futures_util::stream::unfold(
+        state,
+        |mut state| async move {
+            loop {
+                if let Some((entry, depth)) = state.pending.pop_front() {
+                    let within = state.options.max_depth.is_none_or(|max| depth < max);
+                    if entry.is_dir() && within {
+                        state.dirs.push_back((entry.path.clone(), depth));
+                    }
+                    if state.options.matches(&state.root, &entry.path) {
+                        return Some((Ok(entry), state));
+                    }
+                    continue;
+                }
+
+                let (dir, depth) = state.dirs.pop_front()?;
+                match state.backend.list_dir(&dir).await {
+                    Ok(entries) => {
+                        state
+                            .pending
+                            .extend(entries.into_iter().map(|entry| (entry, depth + 1)));
+                    }
+                    Err(err) => return Some((Err(err), state)),
+                }
+            }
+        },
+    ))
+}
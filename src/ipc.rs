@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+
+use crate::{Backend, Unsupported};
+
+/// Inter-process communication.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait Ipc: Backend {
+    /// Sends a message to another process over a named channel.
+    async fn ipc_send(&self, channel: &str, data: &[u8]) -> Result<(), Self::Error> {
+        Err(Unsupported::new("ipc_send").into())
+    }
+
+    /// Receives a message from another process over a named channel.
+    async fn ipc_receive(&self, channel: &str) -> Result<Vec<u8>, Self::Error> {
+        Err(Unsupported::new("ipc_receive").into())
+    }
+}
@@ -1,210 +1,115 @@
-use std::path::Path;
-use std::time::Duration;
-use std::time::SystemTime;
-
-use async_trait::async_trait;
-use http::{Request, Response};
-
-/// `Extio` is a generalized abstraction layer for I/O operations across
-/// files, networking, databases, cloud storage, IPC, and more.
+//! `extio` is a generalized abstraction layer for I/O operations across
+//! files, networking, databases, cloud storage, IPC, and more.
+//!
+//! Each area is exposed as its own capability trait (`FileIo`,
+//! `ObjectStore`, `HttpClient`, `Database`, ...). A backend implements only
+//! the capabilities it actually provides, so code that needs a capability
+//! the backend lacks fails to compile instead of panicking at runtime.
+//! [`Extio`] is the umbrella over all of them.
+//!
+//! Methods a backend leaves at their default return [`Unsupported`] rather
+//! than panicking, and [`Backend::capabilities`] reports what is actually
+//! provided so generic code can check before calling.
+
+pub mod backend;
+mod capability;
+mod crypto;
+pub mod db;
+mod env;
+mod error;
+pub mod fs;
+mod ipc;
+mod logging;
+pub mod mq;
+pub mod net;
+pub mod process;
+mod storage;
+pub mod stream;
+mod time;
+
+pub use crate::capability::{Capabilities, Capability, Unsupported};
+pub use crate::crypto::Crypto;
+pub use crate::db::Database;
+pub use crate::env::Env;
+pub use crate::error::{ErrorKind, ExtioError, HasErrorKind};
+pub use crate::fs::FileIo;
+pub use crate::ipc::Ipc;
+pub use crate::logging::{Logger, Metrics};
+pub use crate::mq::MessageQueue;
+pub use crate::net::{HttpClient, HttpServer, Tcp, Udp, WebSocket};
+pub use crate::process::Exec;
+pub use crate::storage::ObjectStore;
+pub use crate::stream::{ByteReader, ByteStream, ByteWriter};
+pub use crate::time::Clock;
+
+/// Base trait shared by every capability trait.
 ///
-/// Each method has a default `unimplemented!()` body and must be
-/// implemented by a backend or runtime.
-#[allow(unused_variables)]
-#[async_trait]
-pub trait Extio {
+/// Holds the error type so a backend implementing several capabilities
+/// reports failures through a single type.
+pub trait Backend: Send + Sync {
     /// Common error type for all operations.
-    type Error: std::fmt::Debug + Send + Sync + 'static;
-
-    // --- File / Storage ---
-
-    /// Reads the entire contents of a file from disk into memory.
-    ///
-    /// - `path`: Path to the file.
-    /// - Returns: File contents as `Vec<u8>`.
-    fn read_file(&self, path: &Path) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("read_file not implemented")
-    }
-
-    /// Writes binary data into a file, creating or truncating it.
-    ///
-    /// - `path`: Path where the file will be written.
-    /// - `data`: Raw bytes to write.
-    fn write_file(&self, path: &Path, data: &[u8]) -> Result<(), Self::Error> {
-        unimplemented!("write_file not implemented")
-    }
-
-    /// Deletes a file from disk.
     ///
-    /// - `path`: Path to the file.
-    fn delete_file(&self, path: &Path) -> Result<(), Self::Error> {
-        unimplemented!("delete_file not implemented")
-    }
-
-    /// Lists the contents of a directory.
+    /// [`ExtioError`] is provided for backends that do not need their own.
+    type Error: std::fmt::Debug
+        + HasErrorKind
+        + From<Unsupported>
+        + From<std::io::Error>
+        + Send
+        + Sync
+        + 'static;
+
+    /// Returns the capabilities this backend actually provides.
     ///
-    /// - `path`: Directory path.
-    /// - Returns: A vector of file/directory names.
-    fn list_dir(&self, path: &Path) -> Result<Vec<String>, Self::Error> {
-        unimplemented!("list_dir not implemented")
-    }
-
-    // --- Cloud / Object Storage ---
-
-    /// Uploads a blob of binary data to object storage.
-    async fn storage_put(&self, key: &str, data: Vec<u8>) -> Result<(), Self::Error> {
-        unimplemented!("storage_put not implemented")
-    }
-
-    /// Retrieves a blob of binary data from object storage.
-    async fn storage_get(&self, key: &str) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("storage_get not implemented")
-    }
-
-    /// Deletes a blob from object storage.
-    async fn storage_delete(&self, key: &str) -> Result<(), Self::Error> {
-        unimplemented!("storage_delete not implemented")
-    }
-
-    // --- HTTP / Networking ---
-
-    /// Sends an HTTP request and returns the response.
-    async fn http_request(&self, req: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, Self::Error> {
-        unimplemented!("http_request not implemented")
-    }
-
-    /// Sends data over TCP and waits for a response.
-    async fn tcp_send(&self, addr: &str, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("tcp_send not implemented")
-    }
-
-    /// Sends a UDP packet (fire-and-forget).
-    async fn udp_send(&self, addr: &str, data: &[u8]) -> Result<(), Self::Error> {
-        unimplemented!("udp_send not implemented")
-    }
-
-    // --- WebSockets ---
-
-    /// Establishes a WebSocket connection.
-    async fn ws_connect(&self, url: &str) -> Result<(), Self::Error> {
-        unimplemented!("ws_connect not implemented")
-    }
-
-    /// Sends a WebSocket message.
-    async fn ws_send(&self, msg: &[u8]) -> Result<(), Self::Error> {
-        unimplemented!("ws_send not implemented")
-    }
-
-    /// Receives a WebSocket message.
-    async fn ws_receive(&self) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("ws_receive not implemented")
-    }
-
-    // --- Database ---
-
-    /// Executes a database query that returns rows.
-    ///
-    /// - `query`: SQL query string.
-    /// - `params`: Serialized parameters.
-    /// - Returns: Raw result set as bytes.
-    async fn db_query(&self, query: &str, params: &[u8]) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("db_query not implemented")
-    }
-
-    /// Executes a database command (e.g., INSERT/UPDATE/DELETE).
-    ///
-    /// - `query`: SQL statement.
-    /// - `params`: Serialized parameters.
-    /// - Returns: Number of affected rows.
-    async fn db_execute(&self, query: &str, params: &[u8]) -> Result<u64, Self::Error> {
-        unimplemented!("db_execute not implemented")
-    }
-
-    // --- Process Execution ---
-
-    /// Executes a system command with arguments.
-    ///
-    /// - `cmd`: Command name.
-    /// - `args`: Command-line arguments.
-    /// - Returns: (exit code, stdout/stderr output).
-    async fn exec(&self, cmd: &str, args: &[&str]) -> Result<(i32, Vec<u8>), Self::Error> {
-        unimplemented!("exec not implemented")
-    }
-
-    // --- Message Queue / Pub-Sub ---
-
-    /// Publishes a message to a topic in a message queue.
-    async fn mq_publish(&self, topic: &str, data: &[u8]) -> Result<(), Self::Error> {
-        unimplemented!("mq_publish not implemented")
-    }
-
-    /// Consumes a message from a topic in a message queue.
-    async fn mq_consume(&self, topic: &str) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("mq_consume not implemented")
-    }
-
-    // --- IPC (Inter-Process Communication) ---
-
-    /// Sends a message to another process over a named channel.
-    async fn ipc_send(&self, channel: &str, data: &[u8]) -> Result<(), Self::Error> {
-        unimplemented!("ipc_send not implemented")
-    }
-
-    /// Receives a message from another process over a named channel.
-    async fn ipc_receive(&self, channel: &str) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("ipc_receive not implemented")
-    }
-
-    // --- Time & Scheduling ---
-
-    /// Returns the current system time.
-    fn now(&self) -> SystemTime {
-        SystemTime::now()
-    }
-
-    /// Suspends execution for the specified duration.
-    async fn sleep(&self, dur: Duration) {
-        unimplemented!("sleep not implemented")
-    }
-
-    // --- Environment / Config ---
-
-    /// Retrieves the value of an environment variable.
-    fn get_env(&self, key: &str) -> Option<String> {
-        unimplemented!("get_env not implemented")
-    }
-
-    /// Sets an environment variable.
-    fn set_env(&self, key: &str, value: &str) {
-        unimplemented!("set_env not implemented")
-    }
-
-    // --- Logging & Metrics ---
-
-    /// Writes a log entry with a given severity level.
-    fn log(&self, level: &str, msg: &str) {
-        unimplemented!("log not implemented")
-    }
-
-    /// Records a numeric metric (e.g., counter, gauge, histogram).
-    fn record_metric(&self, name: &str, value: f64) {
-        unimplemented!("record_metric not implemented")
-    }
-
-    // --- Cryptography / Secrets ---
-
-    /// Retrieves a secret value (e.g., API key) from secure storage.
-    fn get_secret(&self, key: &str) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("get_secret not implemented")
+    /// Defaults to the empty set; backends should list every capability
+    /// whose methods they implement.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::empty()
     }
+}
 
-    /// Signs data using the backend's cryptographic key.
-    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
-        unimplemented!("sign not implemented")
-    }
+/// Umbrella over every capability trait.
+///
+/// Implemented automatically for any backend that implements all of them.
+/// A backend that provides only some capabilities can still opt in with
+/// empty `impl` blocks for the rest; their methods then return
+/// [`Unsupported`] and [`Backend::capabilities`] tells callers which ones to
+/// avoid.
+pub trait Extio:
+    FileIo
+    + ObjectStore
+    + HttpClient
+    + HttpServer
+    + Tcp
+    + Udp
+    + WebSocket
+    + Database
+    + Exec
+    + MessageQueue
+    + Ipc
+    + Clock
+    + Env
+    + Logger
+    + Metrics
+    + Crypto
+{
+}
 
-    /// Verifies a digital signature.
-    fn verify(&self, data: &[u8], sig: &[u8]) -> Result<bool, Self::Error> {
-        unimplemented!("verify not implemented")
-    }
+impl<T> Extio for T where
+    T: FileIo
+        + ObjectStore
+        + HttpClient
+        + HttpServer
+        + Tcp
+        + Udp
+        + WebSocket
+        + Database
+        + Exec
+        + MessageQueue
+        + Ipc
+        + Clock
+        + Env
+        + Logger
+        + Metrics
+        + Crypto
+{
 }
@@ -0,0 +1,19 @@
+use crate::Backend;
+
+/// Logging.
+#[allow(unused_variables)]
+pub trait Logger: Backend {
+    /// Writes a log entry with a given severity level.
+    ///
+    /// Defaults to discarding the entry.
+    fn log(&self, level: &str, msg: &str) {}
+}
+
+/// Metrics collection.
+#[allow(unused_variables)]
+pub trait Metrics: Backend {
+    /// Records a numeric metric (e.g., counter, gauge, histogram).
+    ///
+    /// Defaults to discarding the sample.
+    fn record_metric(&self, name: &str, value: f64) {}
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+
+/// A message to publish with [`MessageQueue::mq_publish_with`](super::MessageQueue::mq_publish_with).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Message {
+    /// Message body.
+    pub payload: Vec<u8>,
+    /// Application headers delivered alongside the payload.
+    pub headers: BTreeMap<String, String>,
+}
+
+impl Message {
+    /// Creates a message without headers.
+    pub fn new(payload: impl Into<Vec<u8>>) -> Self {
+        Message {
+            payload: payload.into(),
+            headers: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a header.
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Settles a [`Delivery`] with the backend; implemented by backends.
+///
+/// Backends should treat an acknowledger dropped without being settled like
+/// [`requeue`](Self::requeue), so a consumer that crashes or forgets a
+/// delivery does not lose the message.
+#[async_trait]
+pub trait Acknowledger: Send + Sync {
+    /// Error type of the acknowledger; the backend's error type.
+    type Error;
+
+    /// Marks the message as processed.
+    async fn ack(self: Box<Self>) -> Result<(), Self::Error>;
+
+    /// Rejects the message; it is not delivered to this consumer group
+    /// again.
+    async fn nack(self: Box<Self>) -> Result<(), Self::Error>;
+
+    /// Returns the message to the consumer group for redelivery.
+    async fn requeue(self: Box<Self>) -> Result<(), Self::Error>;
+}
+
+/// A message received with [`MessageQueue::mq_consume`](super::MessageQueue::mq_consume).
+///
+/// Settle it with [`ack`](Self::ack), [`nack`](Self::nack) or
+/// [`requeue`](Self::requeue) once processed. A delivery dropped unsettled is
+/// redelivered.
+pub struct Delivery<E> {
+    id: String,
+    payload: Vec<u8>,
+    headers: BTreeMap<String, String>,
+    timestamp: SystemTime,
+    redeliveries: u32,
+    acker: Box<dyn Acknowledger<Error = E>>,
+}
+
+impl<E> Delivery<E> {
+    /// Creates a first delivery of `message`, published at `timestamp`.
+    pub fn new(
+        id: impl Into<String>,
+        message: Message,
+        timestamp: SystemTime,
+        acker: impl Acknowledger<Error = E> + 'static,
+    ) -> Self {
+        Delivery {
+            id: id.into(),
+            payload: message.payload,
+            headers: message.headers,
+            timestamp,
+            redeliveries: 0,
+            acker: Box::new(acker),
+        }
+    }
+
+    /// Sets how many times the message was delivered before.
+    pub fn with_redeliveries(mut self, redeliveries: u32) -> Self {
+        self.redeliveries = redeliveries;
+        self
+    }
+
+    /// Backend-assigned message id, stable across redeliveries.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Message body.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Application headers.
+    pub fn headers(&self) -> &BTreeMap<String, String> {
+        &self.headers
+    }
+
+    /// Value of the header `key`.
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.get(key).map(String::as_str)
+    }
+
+    /// When the message was published.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// How many times the message was delivered before this delivery; `0`
+    /// on first delivery.
+    pub fn redelivery_count(&self) -> u32 {
+        self.redeliveries
+    }
+
+    /// Marks the message as processed.
+    pub async fn ack(self) -> Result<(), E> {
+        self.acker.ack().await
+    }
+
+    /// Rejects the message; it is not delivered to this consumer group
+    /// again.
+    pub async fn nack(self) -> Result<(), E> {
+        self.acker.nack().await
+    }
+
+    /// Returns the message for redelivery, with the redelivery count
+    /// increased.
+    pub async fn requeue(self) -> Result<(), E> {
+        self.acker.requeue().await
+    }
+}
+
+impl<E> fmt::Debug for Delivery<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Delivery")
+            .field("id", &self.id)
+            .field("payload", &self.payload.len())
+            .field("headers", &self.headers)
+            .field("timestamp", &self.timestamp)
+            .field("redeliveries", &self.redeliveries)
+            .finish_non_exhaustive()
+    }
+}
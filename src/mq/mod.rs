@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+
+mod delivery;
+
+pub use self::delivery::{Acknowledger, Delivery, Message};
+
+use crate::{Backend, Unsupported};
+
+/// Consumer group used by [`MessageQueue::mq_consume`].
+pub const DEFAULT_GROUP: &str = "default";
+
+/// Message queue / pub-sub.
+///
+/// Every consumer group receives every message published to a topic;
+/// consumers in the same group share the messages between them, each
+/// message going to one of them. A group consuming a topic for the first
+/// time starts at the oldest message the backend still holds.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait MessageQueue: Backend {
+    /// Publishes a message to a topic in a message queue.
+    ///
+    /// Defaults to [`mq_publish_with`](Self::mq_publish_with) without
+    /// headers.
+    async fn mq_publish(&self, topic: &str, data: &[u8]) -> Result<(), Self::Error> {
+        self.mq_publish_with(topic, Message::new(data)).await?;
+        Ok(())
+    }
+
+    /// Publishes a message with headers.
+    ///
+    /// - Returns: The id the message will be delivered with.
+    async fn mq_publish_with(&self, topic: &str, message: Message) -> Result<String, Self::Error> {
+        Err(Unsupported::new("mq_publish_with").into())
+    }
+
+    /// Waits for the next message of `topic` in the [`DEFAULT_GROUP`].
+    ///
+    /// Defaults to [`mq_consume_group`](Self::mq_consume_group).
+    async fn mq_consume(&self, topic: &str) -> Result<Delivery<Self::Error>, Self::Error> {
+        self.mq_consume_group(topic, DEFAULT_GROUP).await
+    }
+
+    /// Waits for the next message of `topic` for the consumer group `group`.
+    ///
+    /// - Returns: A delivery to settle once processed; see [`Delivery`].
+    async fn mq_consume_group(
+        &self,
+        topic: &str,
+        group: &str,
+    ) -> Result<Delivery<Self::Error>, Self::Error> {
+        Err(Unsupported::new("mq_consume_group").into())
+    }
+}
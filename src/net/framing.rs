@@ -0,0 +1,191 @@
+//! Message framing over byte streams such as [`TcpConnection`].
+//!
+//! [`TcpConnection`]: crate::net::TcpConnection
+
+use std::io;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// Default upper bound on a single frame or line: 16 MiB.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Frames messages with a 4-byte big-endian length prefix.
+#[derive(Debug)]
+pub struct LengthPrefixed<S> {
+    io: BufReader<S>,
+    max_frame_len: usize,
+}
+
+impl<S> LengthPrefixed<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Wraps `io` with the [default](DEFAULT_MAX_FRAME_LEN) frame limit.
+    pub fn new(io: S) -> Self {
+        LengthPrefixed {
+            io: BufReader::new(io),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    /// Rejects frames longer than `max` bytes in either direction.
+    pub fn max_frame_len(mut self, max: usize) -> Self {
+        self.max_frame_len = max;
+        self
+    }
+
+    /// Writes one frame and flushes it.
+    pub async fn send(&mut self, frame: &[u8]) -> io::Result<()> {
+        if frame.len() > self.max_frame_len {
+            return Err(too_long(frame.len(), self.max_frame_len));
+        }
+        let len =
+            u32::try_from(frame.len()).map_err(|_| too_long(frame.len(), u32::MAX as usize))?;
+        let io = self.io.get_mut();
+        io.write_all(&len.to_be_bytes()).await?;
+        io.write_all(frame).await?;
+        io.flush().await
+    }
+
+    /// Reads the next frame.
+    ///
+    /// - Returns: `None` if the peer closed the stream between frames.
+    pub async fn recv(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut header = [0; 4];
+        if self.io.read(&mut header[..1]).await? == 0 {
+            return Ok(None);
+        }
+        self.io.read_exact(&mut header[1..]).await?;
+        let len = u32::from_be_bytes(header) as usize;
+        if len > self.max_frame_len {
+            return Err(too_long(len, self.max_frame_len));
+        }
+        let mut frame = vec![0; len];
+        self.io.read_exact(&mut frame).await?;
+        Ok(Some(frame))
+    }
+
+    /// Returns the underlying stream. Bytes already buffered are lost.
+    pub fn into_inner(self) -> S {
+        self.io.into_inner()
+    }
+}
+
+/// Frames messages as newline-terminated UTF-8 lines.
+#[derive(Debug)]
+pub struct Lines<S> {
+    io: BufReader<S>,
+    max_line_len: usize,
+    /// Set after an oversized line, whose rest is dropped by the next
+    /// `recv`.
+    discarding: bool,
+}
+
+impl<S> Lines<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Wraps `io` with the [default](DEFAULT_MAX_FRAME_LEN) line limit.
+    pub fn new(io: S) -> Self {
+        Lines {
+            io: BufReader::new(io),
+            max_line_len: DEFAULT_MAX_FRAME_LEN,
+            discarding: false,
+        }
+    }
+
+    /// Rejects lines longer than `max` bytes, excluding the terminator.
+    pub fn max_line_len(mut self, max: usize) -> Self {
+        self.max_line_len = max;
+        self
+    }
+
+    /// Writes `line` followed by `\n` and flushes it.
+    ///
+    /// Fails with `InvalidInput` if `line` itself contains a newline.
+    pub async fn send(&mut self, line: &str) -> io::Result<()> {
+        if line.contains('\n') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "line contains a newline",
+            ));
+        }
+        if line.len() > self.max_line_len {
+            return Err(too_long(line.len(), self.max_line_len));
+        }
+        let io = self.io.get_mut();
+        io.write_all(line.as_bytes()).await?;
+        io.write_all(b"\n").await?;
+        io.flush().await
+    }
+
+    /// Reads the next line without its `\n` or `\r\n` terminator.
+    ///
+    /// A line over the limit fails with `InvalidData`; the rest of it is
+    /// skipped, so the next call returns the line after it.
+    ///
+    /// - Returns: `None` at end of stream. A final line without a
+    ///   terminator is still returned.
+    pub async fn recv(&mut self) -> io::Result<Option<String>> {
+        if self.discarding {
+            self.skip_line().await?;
+        }
+        let mut line = Vec::new();
+        let limit = self.max_line_len as u64 + 2;
+        let n = (&mut self.io)
+            .take(limit)
+            .read_until(b'\n', &mut line)
+            .await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        let terminated = line.ends_with(b"\n");
+        if terminated {
+            line.pop();
+            if line.ends_with(b"\r") {
+                line.pop();
+            }
+        }
+        if line.len() > self.max_line_len {
+            self.discarding = !terminated;
+            return Err(too_long(line.len(), self.max_line_len));
+        }
+        String::from_utf8(line)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Drops input up to and including the next `\n`.
+    async fn skip_line(&mut self) -> io::Result<()> {
+        loop {
+            let buf = self.io.fill_buf().await?;
+            if buf.is_empty() {
+                break;
+            }
+            match buf.iter().position(|&b| b == b'\n') {
+                Some(end) => {
+                    self.io.consume(end + 1);
+                    break;
+                }
+                None => {
+                    let len = buf.len();
+                    self.io.consume(len);
+                }
+            }
+        }
+        self.discarding = false;
+        Ok(())
+    }
+
+    /// Returns the underlying stream. Bytes already buffered are lost.
+    pub fn into_inner(self) -> S {
+        self.io.into_inner()
+    }
+}
+
+fn too_long(len: usize, max: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("frame of {len} bytes exceeds the {max} byte limit"),
+    )
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use http::{Request, Response};
+
+use crate::net::Body;
+use crate::{Backend, Unsupported};
+
+/// Outbound HTTP.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait HttpClient: Backend {
+    /// Sends an HTTP request and returns the response.
+    async fn http_request(&self, req: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, Self::Error> {
+        Err(Unsupported::new("http_request").into())
+    }
+
+    /// Sends an HTTP request whose body may be streamed and returns a
+    /// response whose body may be streamed.
+    ///
+    /// Backends with native streaming send the request body as it is
+    /// produced and return the response as soon as the headers arrive, so
+    /// large downloads and server-sent events can be proxied chunk by chunk.
+    ///
+    /// Defaults to buffering both bodies around
+    /// [`http_request`](Self::http_request).
+    async fn http_request_stream(&self, req: Request<Body>) -> Result<Response<Body>, Self::Error> {
+        let (parts, body) = req.into_parts();
+        let body = body.collect().await.map_err(std::io::Error::other)?;
+        let resp = self
+            .http_request(Request::from_parts(parts, body.to_vec()))
+            .await?;
+        Ok(resp.map(Body::from))
+    }
+}
@@ -0,0 +1,17 @@
+//! Networking capabilities: HTTP, TCP, UDP and WebSockets.
+
+mod body;
+pub mod framing;
+mod http;
+mod server;
+mod tcp;
+mod udp;
+mod ws;
+
+pub use self::body::{Body, BoxError};
+pub use self::framing::{LengthPrefixed, Lines};
+pub use self::http::HttpClient;
+pub use self::server::{HttpHandler, HttpServer, ServerHandle};
+pub use self::tcp::{AsyncReadWrite, Tcp, TcpAcceptor, TcpConnection, TcpListenerHandle};
+pub use self::udp::{Udp, UdpSocket, UdpSocketHandle};
+pub use self::ws::{CloseFrame, WebSocket, WsConnection, WsMessage, WsStream};
@@ -0,0 +1,131 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::{Request, Response};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::net::Body;
+use crate::{Backend, Unsupported};
+
+/// Handles requests dispatched by [`HttpServer::http_serve`].
+///
+/// Implemented for any `Fn(Request<Body>) -> impl Future<Output =
+/// Response<Body>>`, so plain async closures can be served.
+#[async_trait]
+pub trait HttpHandler: Send + Sync + 'static {
+    /// Produces the response for one request.
+    async fn handle(&self, req: Request<Body>) -> Response<Body>;
+}
+
+#[async_trait]
+impl<F, Fut> HttpHandler for F
+where
+    F: Fn(Request<Body>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response<Body>> + Send + 'static,
+{
+    async fn handle(&self, req: Request<Body>) -> Response<Body> {
+        self(req).await
+    }
+}
+
+/// Handle to a running server returned by [`HttpServer::http_serve`].
+///
+/// Dropping the handle stops the server, like calling
+/// [`shutdown`](Self::shutdown) without waiting.
+pub struct ServerHandle {
+    addr: String,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+    on_close: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl ServerHandle {
+    /// Creates a handle for a server task.
+    ///
+    /// - `addr`: Address the server is reachable at.
+    /// - `shutdown`: Signals the task to stop; the task must also stop when
+    ///   the sender is dropped.
+    /// - `task`: The server task, awaited by `shutdown`.
+    pub fn new(
+        addr: impl Into<String>,
+        shutdown: oneshot::Sender<()>,
+        task: JoinHandle<()>,
+    ) -> Self {
+        ServerHandle {
+            addr: addr.into(),
+            shutdown: Some(shutdown),
+            task: Some(task),
+            on_close: None,
+        }
+    }
+
+    /// Runs `f` when the handle is dropped or shut down, after the server
+    /// task has been signalled (and, for `shutdown`, has finished).
+    ///
+    /// Unlike cleanup in the server task, `f` has run by the time `drop`
+    /// or `shutdown` returns, so backends can release what the server
+    /// holds (e.g. its address) without racing a new `http_serve`.
+    pub fn on_close(mut self, f: impl FnOnce() + Send + Sync + 'static) -> Self {
+        self.on_close = Some(Box::new(f));
+        self
+    }
+
+    /// Address the server is reachable at, with the actual port if port `0`
+    /// was requested.
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Stops accepting new connections and waits for the server task to
+    /// finish.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+    }
+}
+
+impl fmt::Debug for ServerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerHandle")
+            .field("addr", &self.addr)
+            .field("running", &self.shutdown.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ServerHandle {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(on_close) = self.on_close.take() {
+            on_close();
+        }
+    }
+}
+
+/// Inbound HTTP.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait HttpServer: Backend {
+    /// Binds `addr` and dispatches every incoming request to `handler`.
+    ///
+    /// - `addr`: Address to listen on (e.g. `"127.0.0.1:8080"`; port `0`
+    ///   picks a free one).
+    /// - `handler`: Called once per request.
+    /// - Returns: A handle that keeps the server running until dropped.
+    async fn http_serve(
+        &self,
+        addr: &str,
+        handler: Arc<dyn HttpHandler>,
+    ) -> Result<ServerHandle, Self::Error> {
+        Err(Unsupported::new("http_serve").into())
+    }
+}
@@ -0,0 +1,153 @@
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+use crate::{Backend, Unsupported};
+
+/// Bidirectional byte stream, implemented for every `AsyncRead +
+/// AsyncWrite` type.
+pub trait AsyncReadWrite: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T> AsyncReadWrite for T where T: AsyncRead + AsyncWrite + Send + Unpin {}
+
+/// An open TCP connection returned by [`Tcp::tcp_connect`] and
+/// [`TcpAcceptor::accept`].
+///
+/// Reads and writes go straight to the underlying stream; wrap it in
+/// [`LengthPrefixed`](crate::net::LengthPrefixed) or
+/// [`Lines`](crate::net::Lines) for message framing.
+pub struct TcpConnection {
+    io: Box<dyn AsyncReadWrite>,
+    local_addr: Option<SocketAddr>,
+    peer_addr: Option<SocketAddr>,
+}
+
+impl TcpConnection {
+    /// Wraps a stream whose addresses are unknown (e.g. an in-memory pipe).
+    pub fn new(io: impl AsyncReadWrite + 'static) -> Self {
+        TcpConnection {
+            io: Box::new(io),
+            local_addr: None,
+            peer_addr: None,
+        }
+    }
+
+    /// Wraps a stream with its local and peer addresses.
+    pub fn with_addrs(
+        io: impl AsyncReadWrite + 'static,
+        local_addr: SocketAddr,
+        peer_addr: SocketAddr,
+    ) -> Self {
+        TcpConnection {
+            io: Box::new(io),
+            local_addr: Some(local_addr),
+            peer_addr: Some(peer_addr),
+        }
+    }
+
+    /// Local address of the connection, if known.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Remote address of the connection, if known.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+}
+
+impl std::fmt::Debug for TcpConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpConnection")
+            .field("local_addr", &self.local_addr)
+            .field("peer_addr", &self.peer_addr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AsyncRead for TcpConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for TcpConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_shutdown(cx)
+    }
+}
+
+/// Accepts inbound connections; returned by [`Tcp::tcp_listen`].
+#[async_trait]
+pub trait TcpAcceptor: Send {
+    /// Error type of the acceptor; the backend's error type.
+    type Error;
+
+    /// Waits for the next inbound connection.
+    ///
+    /// - Returns: The connection and the peer's address.
+    async fn accept(&mut self) -> Result<(TcpConnection, SocketAddr), Self::Error>;
+
+    /// Address the listener is bound to, with the actual port if port `0`
+    /// was requested.
+    fn local_addr(&self) -> Result<SocketAddr, Self::Error>;
+}
+
+/// Boxed acceptor returned by [`Tcp::tcp_listen`].
+pub type TcpListenerHandle<E> = Box<dyn TcpAcceptor<Error = E>>;
+
+/// TCP networking.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait Tcp: Backend {
+    /// Opens a TCP connection.
+    ///
+    /// - `addr`: `host:port` to connect to.
+    /// - Returns: A duplex stream that stays open until dropped or shut down.
+    async fn tcp_connect(&self, addr: &str) -> Result<TcpConnection, Self::Error> {
+        Err(Unsupported::new("tcp_connect").into())
+    }
+
+    /// Binds a listener for inbound connections.
+    ///
+    /// - `addr`: Local `host:port`; port `0` picks a free one.
+    async fn tcp_listen(&self, addr: &str) -> Result<TcpListenerHandle<Self::Error>, Self::Error> {
+        Err(Unsupported::new("tcp_listen").into())
+    }
+
+    /// Sends data over TCP and waits for a response.
+    ///
+    /// Connects, writes `data`, shuts down the write half and reads until
+    /// the peer closes the connection, so the response ends at EOF. Use
+    /// [`tcp_connect`](Self::tcp_connect) for anything longer-lived.
+    ///
+    /// Defaults to doing exactly that on top of `tcp_connect`.
+    async fn tcp_send(&self, addr: &str, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        let mut conn = self.tcp_connect(addr).await?;
+        conn.write_all(data).await?;
+        conn.shutdown().await?;
+        let mut response = Vec::new();
+        conn.read_to_end(&mut response).await?;
+        Ok(response)
+    }
+}
@@ -0,0 +1,89 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use async_trait::async_trait;
+
+use crate::{Backend, Unsupported};
+
+/// A bound UDP socket returned by [`Udp::udp_bind`].
+#[async_trait]
+pub trait UdpSocket: Send + Sync {
+    /// Error type of the socket; the backend's error type.
+    type Error;
+
+    /// Sends one datagram to `target`.
+    ///
+    /// - Returns: Number of bytes sent.
+    async fn send_to(&self, data: &[u8], target: SocketAddr) -> Result<usize, Self::Error>;
+
+    /// Waits for one datagram.
+    ///
+    /// - `buf`: Receives the payload; excess bytes of a larger datagram are
+    ///   discarded.
+    /// - Returns: Number of bytes received and the sender's address.
+    async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr), Self::Error>;
+
+    /// Address the socket is bound to.
+    fn local_addr(&self) -> Result<SocketAddr, Self::Error>;
+
+    /// Allows or forbids sending to broadcast addresses.
+    fn set_broadcast(&self, on: bool) -> Result<(), Self::Error>;
+
+    /// Joins an IPv4 multicast group.
+    ///
+    /// - `interface`: Local interface address; `0.0.0.0` lets the system
+    ///   choose.
+    fn join_multicast_v4(&self, group: Ipv4Addr, interface: Ipv4Addr) -> Result<(), Self::Error>;
+
+    /// Leaves an IPv4 multicast group.
+    fn leave_multicast_v4(&self, group: Ipv4Addr, interface: Ipv4Addr) -> Result<(), Self::Error>;
+
+    /// Joins an IPv6 multicast group.
+    ///
+    /// - `interface`: Interface index; `0` lets the system choose.
+    fn join_multicast_v6(&self, group: &Ipv6Addr, interface: u32) -> Result<(), Self::Error>;
+
+    /// Leaves an IPv6 multicast group.
+    fn leave_multicast_v6(&self, group: &Ipv6Addr, interface: u32) -> Result<(), Self::Error>;
+
+    /// Whether multicast datagrams sent by this socket loop back to local
+    /// members (IPv4).
+    fn set_multicast_loop_v4(&self, on: bool) -> Result<(), Self::Error>;
+
+    /// Time-to-live of outgoing IPv4 multicast datagrams.
+    fn set_multicast_ttl_v4(&self, ttl: u32) -> Result<(), Self::Error>;
+}
+
+/// Boxed socket returned by [`Udp::udp_bind`].
+pub type UdpSocketHandle<E> = Box<dyn UdpSocket<Error = E>>;
+
+/// UDP networking.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait Udp: Backend {
+    /// Binds a UDP socket.
+    ///
+    /// - `addr`: Local `host:port`; port `0` picks a free one.
+    async fn udp_bind(&self, addr: &str) -> Result<UdpSocketHandle<Self::Error>, Self::Error> {
+        Err(Unsupported::new("udp_bind").into())
+    }
+
+    /// Sends a UDP packet (fire-and-forget).
+    ///
+    /// Defaults to resolving `addr` and sending from a socket bound to an
+    /// ephemeral port via [`udp_bind`](Self::udp_bind).
+    async fn udp_send(&self, addr: &str, data: &[u8]) -> Result<(), Self::Error> {
+        let target = tokio::net::lookup_host(addr).await?.next().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{addr} did not resolve"),
+            )
+        })?;
+        let local = if target.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        self.udp_bind(local).await?.send_to(data, target).await?;
+        Ok(())
+    }
+}
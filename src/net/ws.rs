@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+
+use crate::{Backend, Unsupported};
+
+/// A WebSocket message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsMessage {
+    /// UTF-8 text frame.
+    Text(String),
+    /// Binary frame.
+    Binary(Vec<u8>),
+    /// Ping control frame. Backends answer pings automatically but still
+    /// report them.
+    Ping(Vec<u8>),
+    /// Pong control frame.
+    Pong(Vec<u8>),
+    /// Close control frame, with the peer's close code and reason if given.
+    Close(Option<CloseFrame>),
+}
+
+/// Close code and reason carried by a close frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseFrame {
+    /// Close status code (RFC 6455 section 7.4).
+    pub code: u16,
+    /// Human-readable reason, possibly empty.
+    pub reason: String,
+}
+
+impl CloseFrame {
+    /// Normal closure.
+    pub const NORMAL: u16 = 1000;
+    /// The endpoint is going away (server shutdown, page navigation).
+    pub const GOING_AWAY: u16 = 1001;
+    /// The peer violated the protocol.
+    pub const PROTOCOL_ERROR: u16 = 1002;
+    /// The endpoint cannot accept this data type.
+    pub const UNSUPPORTED_DATA: u16 = 1003;
+    /// The message violated the endpoint's policy.
+    pub const POLICY_VIOLATION: u16 = 1008;
+    /// The message was too big to process.
+    pub const MESSAGE_TOO_BIG: u16 = 1009;
+    /// The server hit an unexpected condition.
+    pub const INTERNAL_ERROR: u16 = 1011;
+
+    /// Creates a close frame.
+    pub fn new(code: u16, reason: impl Into<String>) -> Self {
+        CloseFrame {
+            code,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// An open WebSocket connection returned by [`WebSocket::ws_connect`].
+#[async_trait]
+pub trait WsConnection: Send {
+    /// Error type of the connection; the backend's error type.
+    type Error;
+
+    /// Sends a message.
+    async fn send(&mut self, msg: WsMessage) -> Result<(), Self::Error>;
+
+    /// Receives the next message.
+    ///
+    /// - Returns: `None` once the connection is closed.
+    async fn receive(&mut self) -> Result<Option<WsMessage>, Self::Error>;
+
+    /// Starts the closing handshake and flushes it.
+    ///
+    /// - `frame`: Close code and reason; `None` sends a close frame without
+    ///   a status.
+    async fn close(&mut self, frame: Option<CloseFrame>) -> Result<(), Self::Error>;
+
+    /// Sends a text frame.
+    async fn send_text(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.send(WsMessage::Text(text.to_owned())).await
+    }
+
+    /// Sends a binary frame.
+    async fn send_binary(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.send(WsMessage::Binary(data.to_vec())).await
+    }
+
+    /// Sends a ping frame.
+    async fn ping(&mut self, payload: &[u8]) -> Result<(), Self::Error> {
+        self.send(WsMessage::Ping(payload.to_vec())).await
+    }
+}
+
+/// Boxed connection handle returned by [`WebSocket::ws_connect`].
+pub type WsStream<E> = Box<dyn WsConnection<Error = E>>;
+
+/// WebSocket client.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait WebSocket: Backend {
+    /// Establishes a WebSocket connection.
+    ///
+    /// - `url`: `ws://` or `wss://` URL.
+    /// - Returns: A handle to the connection; several can be open at once.
+    async fn ws_connect(&self, url: &str) -> Result<WsStream<Self::Error>, Self::Error> {
+        Err(Unsupported::new("ws_connect").into())
+    }
+}
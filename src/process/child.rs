@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+
+use super::ExitStatus;
+use crate::stream::{ByteReader, ByteWriter};
+
+/// Process control behind a [`ChildHandle`], implemented by backends.
+#[async_trait]
+pub trait ChildControl: Send {
+    /// Error type of the child; the backend's error type.
+    type Error;
+
+    /// OS process id, or `None` once the process has been reaped.
+    fn id(&self) -> Option<u32>;
+
+    /// Waits for the process to exit.
+    async fn wait(&mut self) -> Result<ExitStatus, Self::Error>;
+
+    /// Returns the exit status if the process has already exited.
+    fn try_wait(&mut self) -> Result<Option<ExitStatus>, Self::Error>;
+
+    /// Forcibly terminates the process (`SIGKILL` on Unix) and reaps it.
+    async fn kill(&mut self) -> Result<(), Self::Error>;
+}
+
+/// A running process returned by [`Exec::spawn`](crate::Exec::spawn).
+///
+/// The pipes are plain fields so they can be moved into separate tasks,
+/// e.g. one reading stdout line by line while another writes to stdin.
+/// Dropping the handle kills the process if it is still running.
+pub struct ChildHandle<E> {
+    /// Writer for the child's stdin; dropping it closes the pipe.
+    pub stdin: Option<ByteWriter>,
+    /// Reader for the child's stdout.
+    pub stdout: Option<ByteReader>,
+    /// Reader for the child's stderr.
+    pub stderr: Option<ByteReader>,
+    control: Box<dyn ChildControl<Error = E>>,
+}
+
+impl<E> ChildHandle<E> {
+    /// Wraps a backend's process control; the pipes start out as `None`.
+    pub fn new(control: impl ChildControl<Error = E> + 'static) -> Self {
+        ChildHandle {
+            stdin: None,
+            stdout: None,
+            stderr: None,
+            control: Box::new(control),
+        }
+    }
+
+    /// OS process id, or `None` once the process has been reaped.
+    pub fn id(&self) -> Option<u32> {
+        self.control.id()
+    }
+
+    /// Closes stdin, then waits for the process to exit.
+    ///
+    /// Output not read from `stdout`/`stderr` stays in the pipes; a child
+    /// that fills a pipe blocks until it is read, so read the pipes
+    /// concurrently when output can be large.
+    pub async fn wait(&mut self) -> Result<ExitStatus, E> {
+        self.stdin = None;
+        self.control.wait().await
+    }
+
+    /// Returns the exit status if the process has already exited.
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>, E> {
+        self.control.try_wait()
+    }
+
+    /// Forcibly terminates the process and reaps it.
+    pub async fn kill(&mut self) -> Result<(), E> {
+        self.control.kill().await
+    }
+}
+
+impl<E> std::fmt::Debug for ChildHandle<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChildHandle")
+            .field("id", &self.id())
+            .field("stdin", &self.stdin.is_some())
+            .field("stdout", &self.stdout.is_some())
+            .field("stderr", &self.stderr.is_some())
+            .finish_non_exhaustive()
+    }
+}
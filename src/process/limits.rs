@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+/// Resource caps applied to a child process with `setrlimit` (Unix).
+///
+/// Wall-clock time is capped separately by
+/// [`ExecOptions::timeout`](super::ExecOptions::timeout). Backends that
+/// cannot enforce a limit that is set must fail with
+/// [`Unsupported`](crate::Unsupported) rather than ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResourceLimits {
+    /// CPU time (`RLIMIT_CPU`), rounded up to whole seconds and at least
+    /// one, since a limit of zero would kill the process at once. The
+    /// process is killed by `SIGXCPU` or `SIGKILL` when it runs out.
+    pub cpu_time: Option<Duration>,
+    /// Address space in bytes (`RLIMIT_AS`); allocations beyond it fail.
+    pub memory: Option<u64>,
+    /// Largest file the process may write, in bytes (`RLIMIT_FSIZE`).
+    /// Writing past it raises `SIGXFSZ`.
+    pub file_size: Option<u64>,
+    /// Maximum number of open file descriptors (`RLIMIT_NOFILE`).
+    pub open_files: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// No limits.
+    pub fn new() -> Self {
+        ResourceLimits::default()
+    }
+
+    /// Sets [`cpu_time`](Self::cpu_time).
+    pub fn cpu_time(mut self, limit: Duration) -> Self {
+        self.cpu_time = Some(limit);
+        self
+    }
+
+    /// Sets [`memory`](Self::memory).
+    pub fn memory(mut self, bytes: u64) -> Self {
+        self.memory = Some(bytes);
+        self
+    }
+
+    /// Sets [`file_size`](Self::file_size).
+    pub fn file_size(mut self, bytes: u64) -> Self {
+        self.file_size = Some(bytes);
+        self
+    }
+
+    /// Sets [`open_files`](Self::open_files).
+    pub fn open_files(mut self, count: u64) -> Self {
+        self.open_files = Some(count);
+        self
+    }
+
+    /// Returns `true` if no limit is set.
+    pub fn is_empty(&self) -> bool {
+        *self == ResourceLimits::default()
+    }
+
+    /// Combines two sets of limits, keeping the stricter value of each.
+    pub fn min(self, other: ResourceLimits) -> Self {
+        fn min<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+        ResourceLimits {
+            cpu_time: min(self.cpu_time, other.cpu_time),
+            memory: min(self.memory, other.memory),
+            file_size: min(self.file_size, other.file_size),
+            open_files: min(self.open_files, other.open_files),
+        }
+    }
+
+    /// Applies the limits to the current process; meant to run in a forked
+    /// child right before `exec`.
+    #[cfg(unix)]
+    pub(crate) fn apply(&self) -> std::io::Result<()> {
+        let cpu_secs = self.cpu_time.map(|t| {
+            let secs = t.as_secs().saturating_add(u64::from(t.subsec_nanos() > 0));
+            secs.max(1)
+        });
+        let limits = [
+            (libc::RLIMIT_CPU, cpu_secs),
+            (libc::RLIMIT_AS, self.memory),
+            (libc::RLIMIT_FSIZE, self.file_size),
+            (libc::RLIMIT_NOFILE, self.open_files),
+        ];
+        for (resource, value) in limits {
+            let Some(value) = value else { continue };
+            let value = libc::rlim_t::try_from(value).unwrap_or(libc::RLIM_INFINITY);
+            let limit = libc::rlimit {
+                rlim_cur: value,
+                rlim_max: value,
+            };
+            // SAFETY: `setrlimit` is async-signal-safe and only reads `limit`.
+            if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+
+mod child;
+mod limits;
+mod options;
+mod output;
+mod sandbox;
+
+pub use self::child::{ChildControl, ChildHandle};
+pub use self::limits::ResourceLimits;
+pub use self::options::ExecOptions;
+pub use self::output::{ExecOutput, ExitStatus};
+pub use self::sandbox::{CommandRule, Denied, ExecPolicy, Sandboxed};
+
+use crate::{Backend, Unsupported};
+
+/// Process execution.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait Exec: Backend {
+    /// Executes a system command with arguments and waits for it to exit.
+    ///
+    /// - `cmd`: Command name, looked up in `PATH`, or a path.
+    /// - `args`: Command-line arguments.
+    /// - Returns: Exit status and the separately captured stdout and stderr.
+    ///   A non-zero exit is not an error.
+    ///
+    /// Defaults to [`exec_with`](Self::exec_with) with default options.
+    async fn exec(&self, cmd: &str, args: &[&str]) -> Result<ExecOutput, Self::Error> {
+        self.exec_with(cmd, args, &ExecOptions::new()).await
+    }
+
+    /// Executes a command with stdin, working directory, environment and
+    /// timeout taken from `options`.
+    ///
+    /// - Returns: See [`exec`](Self::exec); fails with
+    ///   [`ErrorKind::Timeout`](crate::ErrorKind::Timeout) after killing the
+    ///   child if `options.timeout` elapses.
+    async fn exec_with(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ExecOutput, Self::Error> {
+        Err(Unsupported::new("exec_with").into())
+    }
+
+    /// Starts a command without waiting for it.
+    ///
+    /// - `options`: As for [`exec_with`](Self::exec_with). If `stdin` is
+    ///   set it is fed to the child in the background and the handle has no
+    ///   stdin writer; otherwise stdin is a pipe. A `timeout` counts from
+    ///   the spawn: once it elapses the child is killed whether or not
+    ///   anyone is waiting for it, and [`ChildHandle::wait`] fails with
+    ///   [`ErrorKind::Timeout`](crate::ErrorKind::Timeout).
+    /// - Returns: A handle with piped stdout and stderr.
+    async fn spawn(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ChildHandle<Self::Error>, Self::Error> {
+        Err(Unsupported::new("spawn").into())
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::ResourceLimits;
+
+/// Options for [`Exec::exec_with`](crate::Exec::exec_with).
+///
+/// The default (`ExecOptions::new()`) runs the command in the current
+/// directory with the current environment, no stdin, no timeout and no
+/// resource limits.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ExecOptions {
+    /// Bytes written to the child's stdin, which is then closed. `None`
+    /// connects stdin to the null device.
+    pub stdin: Option<Vec<u8>>,
+    /// Working directory of the child.
+    pub cwd: Option<PathBuf>,
+    /// Environment changes applied in order on top of the inherited (or,
+    /// with [`env_clear`](Self::env_clear), empty) environment; `None`
+    /// removes the variable.
+    pub env: Vec<(String, Option<String>)>,
+    /// Start from an empty environment instead of inheriting the parent's.
+    pub env_clear: bool,
+    /// Kill the child and fail with
+    /// [`ErrorKind::Timeout`](crate::ErrorKind::Timeout) if it has not exited
+    /// after this long.
+    pub timeout: Option<Duration>,
+    /// CPU, memory and file caps for the child.
+    pub limits: ResourceLimits,
+}
+
+impl ExecOptions {
+    /// Inherit everything, no stdin, no timeout, no limits.
+    pub fn new() -> Self {
+        ExecOptions::default()
+    }
+
+    /// Sets [`stdin`](Self::stdin).
+    pub fn stdin(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.stdin = Some(data.into());
+        self
+    }
+
+    /// Sets [`cwd`](Self::cwd).
+    pub fn cwd(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(dir.into());
+        self
+    }
+
+    /// Sets the variable `key` to `value` in the child.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), Some(value.into())));
+        self
+    }
+
+    /// Removes the variable `key` from the child's environment.
+    pub fn env_remove(mut self, key: impl Into<String>) -> Self {
+        self.env.push((key.into(), None));
+        self
+    }
+
+    /// Sets [`env_clear`](Self::env_clear).
+    pub fn env_clear(mut self, clear: bool) -> Self {
+        self.env_clear = clear;
+        self
+    }
+
+    /// Sets [`timeout`](Self::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets [`limits`](Self::limits).
+    pub fn limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
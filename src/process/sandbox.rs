@@ -0,0 +1,356 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use glob::Pattern;
+
+use super::{ChildHandle, Exec, ExecOptions, ExecOutput, ResourceLimits};
+use crate::{Backend, Capabilities, Capability, ExtioError};
+
+/// Error returned when an [`ExecPolicy`] refuses a call.
+///
+/// Reported as [`ErrorKind::Denied`](crate::ErrorKind::Denied); backend
+/// error types wrapped by [`Sandboxed`] must convert from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Denied {
+    reason: String,
+}
+
+impl Denied {
+    /// Creates an error with the given reason.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Denied {
+            reason: reason.into(),
+        }
+    }
+
+    /// Why the call was refused.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for Denied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "denied by policy: {}", self.reason)
+    }
+}
+
+impl std::error::Error for Denied {}
+
+/// An allowed command and the arguments it may take.
+#[derive(Debug, Clone)]
+pub struct CommandRule {
+    command: String,
+    any_args: bool,
+    patterns: Vec<Pattern>,
+    max_args: Option<usize>,
+}
+
+impl CommandRule {
+    /// Allows `command`, compared literally with the `cmd` passed to
+    /// [`Exec::exec`], with no arguments.
+    ///
+    /// A bare name is looked up in the parent's `PATH` when the call is
+    /// checked; anything else must be an absolute path.
+    pub fn new(command: impl Into<String>) -> Self {
+        CommandRule {
+            command: command.into(),
+            any_args: false,
+            patterns: Vec::new(),
+            max_args: None,
+        }
+    }
+
+    /// Allows any arguments.
+    pub fn any_args(mut self) -> Self {
+        self.any_args = true;
+        self
+    }
+
+    /// Allows arguments matching the glob `pattern` (e.g. `"--format=*"`).
+    ///
+    /// Every argument must match at least one pattern. `*` also matches
+    /// `/`, so use literal patterns for arguments naming paths.
+    pub fn arg(mut self, pattern: &str) -> Result<Self, ExtioError> {
+        let pattern = Pattern::new(pattern)
+            .map_err(|err| ExtioError::InvalidInput(format!("glob `{pattern}`: {err}")))?;
+        self.patterns.push(pattern);
+        Ok(self)
+    }
+
+    /// Caps the number of arguments.
+    pub fn max_args(mut self, max: usize) -> Self {
+        self.max_args = Some(max);
+        self
+    }
+
+    fn check(&self, args: &[&str]) -> Result<(), Denied> {
+        if let Some(max) = self.max_args
+            && args.len() > max
+        {
+            return Err(Denied::new(format!(
+                "`{}` takes at most {max} arguments",
+                self.command
+            )));
+        }
+        if self.any_args {
+            return Ok(());
+        }
+        match args
+            .iter()
+            .find(|arg| !self.patterns.iter().any(|p| p.matches(arg)))
+        {
+            Some(arg) => Err(Denied::new(format!(
+                "argument `{arg}` is not allowed for `{}`",
+                self.command
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// What a [`Sandboxed`] backend lets callers run.
+///
+/// Starts out denying every command. Commands are resolved to an absolute
+/// path before they run, so the child's `PATH` cannot change which program
+/// an allowed name starts.
+///
+/// The child's environment is always rebuilt from scratch: only variables
+/// named with [`inherit_env`](Self::inherit_env) are copied from the
+/// parent, and callers may set only those named with
+/// [`allow_env_override`](Self::allow_env_override).
+#[derive(Debug, Clone, Default)]
+pub struct ExecPolicy {
+    commands: HashMap<String, CommandRule>,
+    inherit_env: BTreeSet<String>,
+    override_env: BTreeSet<String>,
+    cwds: Vec<PathBuf>,
+    limits: ResourceLimits,
+    timeout: Option<Duration>,
+}
+
+impl ExecPolicy {
+    /// A policy that denies everything.
+    pub fn new() -> Self {
+        ExecPolicy::default()
+    }
+
+    /// Allows a command, replacing any earlier rule for it.
+    pub fn allow(mut self, rule: CommandRule) -> Self {
+        self.commands.insert(rule.command.clone(), rule);
+        self
+    }
+
+    /// Passes the parent's `key` through to children.
+    pub fn inherit_env(mut self, key: impl Into<String>) -> Self {
+        self.inherit_env.insert(key.into());
+        self
+    }
+
+    /// Lets callers set `key` for children.
+    ///
+    /// Think twice before allowing `PATH`, `LD_PRELOAD` and the like: they
+    /// change what the child runs.
+    pub fn allow_env_override(mut self, key: impl Into<String>) -> Self {
+        self.override_env.insert(key.into());
+        self
+    }
+
+    /// Lets callers run children in `dir` or below it.
+    ///
+    /// Without any such rule the caller's working directory is not
+    /// checked. `dir` should be absolute; the check is lexical, so
+    /// symlinks inside `dir` are followed.
+    pub fn allow_cwd(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cwds.push(dir.into());
+        self
+    }
+
+    /// Resource caps for every child; callers may only tighten them.
+    pub fn limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Wall-clock cap for every child; callers may only shorten it.
+    ///
+    /// Like [`limits`](Self::limits) it is enforced by the inner backend,
+    /// which kills a spawned child at the deadline even if its handle is
+    /// never waited on.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Checks a call against the policy.
+    ///
+    /// - Returns: The absolute path of the program to run, and the options
+    ///   to run it with: scrubbed environment and the stricter of the
+    ///   policy's and the caller's limits and timeout.
+    pub fn check(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        options: &ExecOptions,
+    ) -> Result<(String, ExecOptions), Denied> {
+        let rule = self
+            .commands
+            .get(cmd)
+            .ok_or_else(|| Denied::new(format!("command `{cmd}` is not allowed")))?;
+        rule.check(args)?;
+        if let Some((key, _)) = options
+            .env
+            .iter()
+            .find(|(key, value)| value.is_some() && !self.override_env.contains(key))
+        {
+            return Err(Denied::new(format!(
+                "environment variable `{key}` may not be set"
+            )));
+        }
+        if let Some(cwd) = &options.cwd
+            && !self.cwds.is_empty()
+            && !self.cwds.iter().any(|dir| within(cwd, dir))
+        {
+            return Err(Denied::new(format!(
+                "working directory {} is not allowed",
+                cwd.display()
+            )));
+        }
+        let program = resolve(cmd)?;
+
+        let mut env = Vec::new();
+        if !options.env_clear {
+            for key in &self.inherit_env {
+                if let Ok(value) = std::env::var(key) {
+                    env.push((key.clone(), Some(value)));
+                }
+            }
+        }
+        env.extend(options.env.iter().cloned());
+
+        let timeout = match (self.timeout, options.timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        let options = ExecOptions {
+            stdin: options.stdin.clone(),
+            cwd: options.cwd.clone(),
+            env,
+            env_clear: true,
+            timeout,
+            limits: self.limits.min(options.limits),
+        };
+        Ok((program, options))
+    }
+}
+
+/// Whether `path` is `dir` or below it, without `..` to climb back out.
+fn within(path: &Path, dir: &Path) -> bool {
+    path.is_absolute()
+        && path.starts_with(dir)
+        && !path.components().any(|c| c == Component::ParentDir)
+}
+
+/// Finds `cmd` in the parent's `PATH` if it is a bare name.
+fn resolve(cmd: &str) -> Result<String, Denied> {
+    let path = Path::new(cmd);
+    if path.is_absolute() {
+        return Ok(cmd.to_owned());
+    }
+    if cmd.is_empty() || path.components().ne([Component::Normal(cmd.as_ref())]) {
+        return Err(Denied::new(format!(
+            "`{cmd}` is neither a bare name nor an absolute path"
+        )));
+    }
+    std::env::var_os("PATH")
+        .iter()
+        .flat_map(std::env::split_paths)
+        .filter(|dir| dir.is_absolute())
+        .map(|dir| dir.join(cmd))
+        .filter(|candidate| is_executable(candidate))
+        .find_map(|candidate| candidate.into_os_string().into_string().ok())
+        .ok_or_else(|| Denied::new(format!("`{cmd}` was not found on PATH")))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// [`Exec`] wrapper that runs only what an [`ExecPolicy`] allows.
+///
+/// Refused calls fail with [`Denied`] before anything is started; allowed
+/// ones run on the inner backend with the environment scrubbed and the
+/// policy's limits applied. Other capabilities of the inner backend are not
+/// exposed.
+#[derive(Debug, Clone)]
+pub struct Sandboxed<B> {
+    inner: B,
+    policy: ExecPolicy,
+}
+
+impl<B> Sandboxed<B> {
+    /// Wraps `inner` with `policy`.
+    pub fn new(inner: B, policy: ExecPolicy) -> Self {
+        Sandboxed { inner, policy }
+    }
+
+    /// The wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// The policy in force.
+    pub fn policy(&self) -> &ExecPolicy {
+        &self.policy
+    }
+}
+
+impl<B: Backend> Backend for Sandboxed<B> {
+    type Error = B::Error;
+
+    fn capabilities(&self) -> Capabilities {
+        let mut capabilities = Capabilities::empty();
+        if self.inner.capabilities().contains(Capability::Exec) {
+            capabilities.insert(Capability::Exec);
+        }
+        capabilities
+    }
+}
+
+#[async_trait]
+impl<B> Exec for Sandboxed<B>
+where
+    B: Exec,
+    B::Error: From<Denied>,
+{
+    async fn exec_with(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ExecOutput, Self::Error> {
+        let (program, options) = self.policy.check(cmd, args, options)?;
+        self.inner.exec_with(&program, args, &options).await
+    }
+
+    async fn spawn(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        options: &ExecOptions,
+    ) -> Result<ChildHandle<Self::Error>, Self::Error> {
+        let (program, options) = self.policy.check(cmd, args, options)?;
+        self.inner.spawn(&program, args, &options).await
+    }
+}
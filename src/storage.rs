@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+
+use crate::stream::{ByteReader, ByteStream, read_all, stream_from_bytes};
+use crate::{Backend, Unsupported};
+
+/// Cloud / object storage operations.
+#[allow(unused_variables)]
+#[async_trait]
+pub trait ObjectStore: Backend {
+    /// Uploads a blob of binary data to object storage.
+    async fn storage_put(&self, key: &str, data: Vec<u8>) -> Result<(), Self::Error> {
+        Err(Unsupported::new("storage_put").into())
+    }
+
+    /// Retrieves a blob of binary data from object storage.
+    async fn storage_get(&self, key: &str) -> Result<Vec<u8>, Self::Error> {
+        Err(Unsupported::new("storage_get").into())
+    }
+
+    /// Deletes a blob from object storage.
+    async fn storage_delete(&self, key: &str) -> Result<(), Self::Error> {
+        Err(Unsupported::new("storage_delete").into())
+    }
+
+    /// Uploads a blob from an asynchronous reader without buffering it.
+    ///
+    /// - Returns: Number of bytes uploaded.
+    ///
+    /// Defaults to reading `reader` into memory and calling
+    /// [`storage_put`](Self::storage_put).
+    async fn storage_put_stream(&self, key: &str, reader: ByteReader) -> Result<u64, Self::Error> {
+        let data = read_all(reader).await?;
+        let len = data.len() as u64;
+        self.storage_put(key, data).await?;
+        Ok(len)
+    }
+
+    /// Retrieves a blob as a stream of chunks without buffering it.
+    ///
+    /// Defaults to a single chunk from [`storage_get`](Self::storage_get).
+    async fn storage_get_stream(&self, key: &str) -> Result<ByteStream<Self::Error>, Self::Error> {
+        let data = self.storage_get(key).await?;
+        Ok(stream_from_bytes(data))
+    }
+}
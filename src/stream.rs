@@ -0,0 +1,39 @@
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+
+/// Boxed asynchronous reader used by the streaming file and storage methods.
+pub type ByteReader = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Boxed asynchronous writer, e.g. the stdin of a spawned process.
+pub type ByteWriter = Pin<Box<dyn AsyncWrite + Send>>;
+
+/// Boxed stream of byte chunks used by the streaming storage methods.
+pub type ByteStream<E> = Pin<Box<dyn Stream<Item = Result<Bytes, E>> + Send>>;
+
+/// Wraps an in-memory buffer as a [`ByteReader`].
+pub fn reader_from_bytes(data: impl Into<Bytes>) -> ByteReader {
+    Box::pin(std::io::Cursor::new(data.into()))
+}
+
+/// Wraps an in-memory buffer as a single-chunk [`ByteStream`].
+pub fn stream_from_bytes<E>(data: impl Into<Bytes>) -> ByteStream<E>
+where
+    E: Send + 'static,
+{
+    Box::pin(futures_util::stream::once(std::future::ready(Ok(
+        data.into()
+    ))))
+}
+
+/// Reads `reader` to the end into memory.
+///
+/// Used by the default streaming methods, which fall back to the buffered
+/// ones for backends that have no native streaming support.
+pub(crate) async fn read_all(mut reader: ByteReader) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await?;
+    Ok(buf)
+}
@@ -0,0 +1,22 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+
+use crate::Backend;
+
+/// Time and scheduling.
+#[async_trait]
+pub trait Clock: Backend {
+    /// Returns the current system time.
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    /// Suspends execution for the specified duration.
+    ///
+    /// Defaults to the tokio timer.
+    async fn sleep(&self, dur: Duration) {
+        tokio::time::sleep(dur).await
+    }
+}
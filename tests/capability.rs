@@ -0,0 +1,54 @@
+use extio::{Capabilities, Capability, Unsupported};
+
+#[test]
+fn missing_and_contains_all() {
+    let have = Capabilities::from([Capability::Files, Capability::Http, Capability::Clock]);
+    let want = Capabilities::from([Capability::Http, Capability::Db, Capability::Clock]);
+
+    assert!(!have.contains_all(want));
+    assert_eq!(have.missing(want), Capability::Db.into());
+    assert!((have | Capability::Db).contains_all(want));
+
+    let subset = Capabilities::from(Capability::Files) | Capability::Clock;
+    assert!(have.contains_all(subset));
+    assert!(have.missing(subset).is_empty());
+    assert!(have.contains_all(Capabilities::empty()));
+    assert_eq!(Capabilities::empty().missing(want), want);
+}
+
+#[test]
+fn iterates_in_declaration_order() {
+    let caps: Capabilities = [Capability::Signing, Capability::Files, Capability::Mq]
+        .into_iter()
+        .collect();
+    let listed: Vec<_> = caps.iter().collect();
+    assert_eq!(
+        listed,
+        [Capability::Files, Capability::Mq, Capability::Signing]
+    );
+    assert_eq!(listed.into_iter().collect::<Capabilities>(), caps);
+
+    let all: Capabilities = Capability::ALL.into_iter().collect();
+    assert!(all.iter().eq(Capability::ALL));
+    assert_eq!(format!("{:?}", caps), "{Files, Mq, Signing}");
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut caps = Capabilities::empty().with(Capability::Tcp);
+    caps.remove(Capability::Udp);
+    assert_eq!(caps, Capability::Tcp.into());
+
+    caps.insert(Capability::Tcp);
+    caps.remove(Capability::Tcp);
+    assert!(caps.is_empty());
+    caps.remove(Capability::Tcp);
+    assert!(caps.is_empty());
+}
+
+#[test]
+fn unsupported_names_the_operation() {
+    let err = Unsupported::new("db_query");
+    assert_eq!(err.operation(), "db_query");
+    assert_eq!(err.to_string(), "db_query is not supported by this backend");
+}
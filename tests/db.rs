@@ -0,0 +1,152 @@
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use extio::db::{DbValue, ResultSet, Statement, Transaction};
+use extio::{ErrorKind, ExtioError, HasErrorKind};
+use serde::Deserialize;
+
+fn account_rows() -> ResultSet {
+    let opened = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 5);
+    let mut rows = ResultSet::new(["id", "owner", "balance", "active", "note", "opened", "kind"]);
+    rows.push(vec![
+        1.into(),
+        "alice".into(),
+        12.5.into(),
+        DbValue::Int(1),
+        DbValue::Null,
+        opened.into(),
+        "checking".into(),
+    ]);
+    rows.push(vec![
+        2.into(),
+        "bob".into(),
+        DbValue::Int(7),
+        false.into(),
+        Some("vip").into(),
+        opened.into(),
+        "savings".into(),
+    ]);
+    rows
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Checking,
+    Savings,
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    id: u32,
+    owner: String,
+    balance: f64,
+    active: bool,
+    note: Option<String>,
+    opened: SystemTime,
+    kind: Kind,
+}
+
+#[test]
+fn typed_getters() {
+    let rows = account_rows();
+    assert_eq!(rows.columns()[1], "owner");
+    let row = &rows.rows()[0];
+    assert_eq!(row.get::<i64>("id").unwrap(), 1);
+    assert_eq!(row.get::<String>("owner").unwrap(), "alice");
+    assert_eq!(row.get::<Option<String>>("note").unwrap(), None);
+    assert!(row.get::<bool>("active").unwrap());
+    assert_eq!(row.get_at::<f64>(2).unwrap(), 12.5);
+
+    let err = row.get::<i64>("owner").unwrap_err();
+    assert_eq!(err.to_string(), "column `owner`: expected int, found text");
+    assert!(row.get::<i64>("missing").is_err());
+}
+
+#[test]
+fn decode_rows_with_serde() {
+    let accounts: Vec<Account> = account_rows().decode().unwrap();
+    assert_eq!(accounts[0].owner, "alice");
+    assert_eq!(accounts[0].id, 1);
+    assert!(accounts[0].active);
+    assert_eq!(accounts[1].balance, 7.0);
+    assert_eq!(accounts[1].note.as_deref(), Some("vip"));
+    assert_eq!(accounts[1].kind, Kind::Savings);
+    assert_eq!(accounts[0].kind, Kind::Checking);
+    assert_eq!(
+        accounts[0].opened,
+        SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 5)
+    );
+
+    let mut counts = ResultSet::new(["count"]);
+    counts.push(vec![3.into()]);
+    assert_eq!(counts.decode::<i64>().unwrap(), [3]);
+
+    let pairs: Vec<(i64, String)> = {
+        let mut rows = ResultSet::new(["id", "owner"]);
+        rows.push(vec![1.into(), "alice".into()]);
+        rows.decode().unwrap()
+    };
+    assert_eq!(pairs, [(1, "alice".to_owned())]);
+
+    let err = account_rows().decode::<i64>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+/// Records the SQL it is asked to run.
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+#[async_trait]
+impl Transaction for Recorder {
+    type Error = ExtioError;
+
+    async fn query(&mut self, query: &str, _: &[DbValue]) -> Result<ResultSet, ExtioError> {
+        self.0.push(query.to_owned());
+        Ok(ResultSet::new(Vec::<String>::new()))
+    }
+
+    async fn execute(&mut self, query: &str, _: &[DbValue]) -> Result<u64, ExtioError> {
+        self.0.push(query.to_owned());
+        Ok(0)
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), ExtioError> {
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), ExtioError> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn savepoint_defaults() {
+    let mut tx = Recorder::default();
+    tx.savepoint("before_fee").await.unwrap();
+    tx.rollback_to("before_fee").await.unwrap();
+    tx.release("before_fee").await.unwrap();
+    assert_eq!(
+        tx.0,
+        [
+            "SAVEPOINT before_fee",
+            "ROLLBACK TO SAVEPOINT before_fee",
+            "RELEASE SAVEPOINT before_fee",
+        ]
+    );
+
+    let err = tx.savepoint("x; DROP TABLE t").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(tx.0.len(), 3);
+}
+
+#[test]
+fn statement_derefs_to_sql() {
+    let stmt = Statement::new("SELECT ?1")
+        .with_params(1)
+        .with_columns(["?1"]);
+    let sql: &str = &stmt;
+    assert_eq!(sql, "SELECT ?1");
+    assert_eq!(stmt.params(), Some(1));
+    assert_eq!(stmt.columns().unwrap(), ["?1"]);
+}
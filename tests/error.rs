@@ -0,0 +1,41 @@
+use std::error::Error as _;
+use std::io;
+
+use extio::{ErrorKind, ExtioError, HasErrorKind, Unsupported};
+
+#[test]
+fn io_errors_keep_their_kind() {
+    for (io_kind, kind) in [
+        (io::ErrorKind::NotFound, ErrorKind::NotFound),
+        (io::ErrorKind::TimedOut, ErrorKind::Timeout),
+        (io::ErrorKind::InvalidData, ErrorKind::InvalidInput),
+        (io::ErrorKind::Unsupported, ErrorKind::Unsupported),
+        (io::ErrorKind::Other, ErrorKind::Backend),
+    ] {
+        let err = ExtioError::from(io::Error::new(io_kind, "boom"));
+        assert_eq!(err.kind(), kind, "{io_kind:?}");
+    }
+
+    let err = ExtioError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+    assert_eq!(err.to_string(), "not found: no such file");
+}
+
+#[test]
+fn unsupported_is_its_own_kind() {
+    let err = ExtioError::from(Unsupported::new("db_query"));
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+    assert_eq!(err.to_string(), "db_query is not supported by this backend");
+    assert!(err.source().unwrap().is::<Unsupported>());
+
+    let err = ExtioError::from(io::Error::new(io::ErrorKind::Unsupported, "no xattrs here"));
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+    assert_eq!(err.to_string(), "no xattrs here");
+}
+
+#[test]
+fn backend_keeps_its_source() {
+    let err = ExtioError::backend(io::Error::other("disk on fire"));
+    assert_eq!(err.kind(), ErrorKind::Backend);
+    assert_eq!(err.to_string(), "disk on fire");
+    assert!(err.source().unwrap().is::<io::Error>());
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use extio::backend::InProcessHttp;
+use extio::net::Body;
+use extio::{ErrorKind, HasErrorKind, HttpClient, HttpServer};
+use http::{Request, Response, StatusCode};
+
+async fn echo(req: Request<Body>) -> Response<Body> {
+    let path = req.uri().path().to_owned();
+    let body = req.into_body().collect().await.unwrap();
+    Response::builder()
+        .header("x-path", path)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn in_process_dispatch() {
+    let transport = InProcessHttp::new();
+    let server = transport
+        .http_serve("api.test", Arc::new(echo))
+        .await
+        .unwrap();
+    assert_eq!(
+        transport
+            .http_serve("api.test", Arc::new(echo))
+            .await
+            .unwrap_err()
+            .kind(),
+        ErrorKind::AlreadyExists
+    );
+
+    let client = transport.clone();
+    let req = Request::post("http://api.test/items")
+        .body(b"hello".to_vec())
+        .unwrap();
+    let resp = client.http_request(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["x-path"], "/items");
+    assert_eq!(resp.body(), b"hello");
+
+    server.shutdown().await;
+    let req = Request::get("http://api.test/").body(Vec::new()).unwrap();
+    assert_eq!(
+        client.http_request(req).await.unwrap_err().kind(),
+        ErrorKind::ConnectionRefused
+    );
+
+    // Dropping a handle frees the address immediately.
+    for _ in 0..100 {
+        let server = transport
+            .http_serve("api.test", Arc::new(echo))
+            .await
+            .unwrap();
+        drop(server);
+    }
+}
+
+#[cfg(all(feature = "hyper", feature = "reqwest"))]
+#[tokio::test]
+async fn hyper_serves_reqwest() {
+    use extio::backend::{HyperServer, ReqwestHttp};
+
+    let server = HyperServer::new()
+        .http_serve("127.0.0.1:0", Arc::new(echo))
+        .await
+        .unwrap();
+    let client = ReqwestHttp::builder().no_proxy().build().unwrap();
+
+    let req = Request::put(format!("http://{}/upload", server.addr()))
+        .body(b"payload".to_vec())
+        .unwrap();
+    let resp = client.http_request(req).await.unwrap();
+    assert_eq!(resp.headers()["x-path"], "/upload");
+    assert_eq!(resp.body(), b"payload");
+
+    let addr = server.addr().to_owned();
+    server.shutdown().await;
+    // A fresh client, since pooled connections outlive the listener.
+    let client = ReqwestHttp::builder().no_proxy().build().unwrap();
+    let req = Request::get(format!("http://{addr}/"))
+        .body(Vec::new())
+        .unwrap();
+    assert_eq!(
+        client.http_request(req).await.unwrap_err().kind(),
+        ErrorKind::ConnectionRefused
+    );
+}
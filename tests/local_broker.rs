@@ -0,0 +1,190 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::Duration;
+
+use extio::backend::LocalBroker;
+use extio::mq::Message;
+use extio::{Backend, Capability, ErrorKind, HasErrorKind, MessageQueue};
+
+async fn idle<T>(consume: impl Future<Output = T>) -> bool {
+    tokio::time::timeout(Duration::from_millis(20), consume)
+        .await
+        .is_err()
+}
+
+#[tokio::test]
+async fn fans_out_to_groups() {
+    let broker = LocalBroker::new();
+    assert!(broker.capabilities().contains(Capability::Mq));
+
+    // Groups registered before publishing see every message.
+    assert!(idle(broker.mq_consume_group("jobs", "audit")).await);
+    for job in ["a", "b", "c"] {
+        broker.mq_publish("jobs", job.as_bytes()).await.unwrap();
+    }
+
+    let one = broker.mq_consume_group("jobs", "workers").await.unwrap();
+    let two = broker.mq_consume_group("jobs", "workers").await.unwrap();
+    assert_eq!((one.payload(), two.payload()), (&b"a"[..], &b"b"[..]));
+    two.requeue().await.unwrap();
+    one.ack().await.unwrap();
+    let again = broker.mq_consume_group("jobs", "workers").await.unwrap();
+    assert_eq!((again.id(), again.redelivery_count()), ("1", 1));
+    again.nack().await.unwrap();
+
+    for (id, job) in [("0", "a"), ("1", "b"), ("2", "c")] {
+        let delivery = broker.mq_consume_group("jobs", "audit").await.unwrap();
+        assert_eq!((delivery.id(), delivery.payload()), (id, job.as_bytes()));
+        delivery.ack().await.unwrap();
+    }
+    assert!(idle(broker.mq_consume_group("jobs", "audit")).await);
+    // "c" is still waiting for the workers.
+    assert_eq!(broker.buffered("jobs"), 1);
+
+    let late = broker.mq_consume("jobs").await.unwrap();
+    assert_eq!(late.payload(), b"c");
+}
+
+#[tokio::test]
+async fn bounded_buffer_applies_backpressure() {
+    let broker = LocalBroker::builder().capacity(2).build();
+    broker.mq_publish("events", b"1").await.unwrap();
+    broker.mq_publish("events", b"2").await.unwrap();
+    assert!(idle(broker.mq_publish("events", b"3")).await);
+
+    let publisher = tokio::spawn({
+        let broker = broker.clone();
+        async move { broker.mq_publish("events", b"3").await }
+    });
+    let first = broker.mq_consume("events").await.unwrap();
+    tokio::task::yield_now().await;
+    assert!(!publisher.is_finished());
+    first.ack().await.unwrap();
+    publisher.await.unwrap().unwrap();
+
+    let mut seen = Vec::new();
+    for _ in 0..2 {
+        let delivery = broker.mq_consume("events").await.unwrap();
+        seen.push(delivery.payload().to_vec());
+        delivery.ack().await.unwrap();
+    }
+    assert_eq!(seen, [b"2", b"3"]);
+    assert_eq!(broker.buffered("events"), 0);
+}
+
+#[tokio::test]
+async fn log_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broker.log");
+
+    let broker = LocalBroker::open(&path).unwrap();
+    broker.mq_publish("orders", b"o-0").await.unwrap();
+    broker
+        .mq_publish_with("orders", Message::new("o-1").header("tenant", "acme"))
+        .await
+        .unwrap();
+    broker.mq_publish("orders", b"o-2").await.unwrap();
+    let settled = broker.mq_consume("orders").await.unwrap();
+    settled.ack().await.unwrap();
+    // o-1 is delivered but never settled before the restart.
+    let unsettled = broker.mq_consume("orders").await.unwrap();
+    let published = unsettled.timestamp();
+    std::mem::forget(unsettled);
+    let settled = broker.mq_consume("orders").await.unwrap();
+    assert_eq!(settled.payload(), b"o-2");
+    settled.ack().await.unwrap();
+    drop(broker);
+
+    let broker = LocalBroker::open(&path).unwrap();
+    let delivery = broker.mq_consume("orders").await.unwrap();
+    assert_eq!((delivery.id(), delivery.payload()), ("1", &b"o-1"[..]));
+    assert_eq!(delivery.header("tenant"), Some("acme"));
+    assert_eq!(delivery.timestamp(), published);
+    assert!(idle(broker.mq_consume("orders")).await);
+    delivery.ack().await.unwrap();
+    assert_eq!(broker.buffered("orders"), 0);
+    broker.mq_publish("orders", b"o-3").await.unwrap();
+    drop(broker);
+
+    // A record torn by a crash mid-write is dropped.
+    let mut log = OpenOptions::new().append(true).open(&path).unwrap();
+    log.write_all(&[40, 0, 0, 0, 1, 6]).unwrap();
+    drop(log);
+
+    let broker = LocalBroker::open(&path).unwrap();
+    let delivery = broker.mq_consume("orders").await.unwrap();
+    assert_eq!((delivery.id(), delivery.payload()), ("3", &b"o-3"[..]));
+    delivery.ack().await.unwrap();
+    // A new group starts at the oldest message still buffered.
+    broker.mq_publish("orders", b"o-4").await.unwrap();
+    let fresh = broker.mq_consume_group("orders", "reports").await.unwrap();
+    assert_eq!(fresh.id(), "4");
+}
+
+#[tokio::test]
+async fn damaged_tail_is_dropped() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broker.log");
+
+    let broker = LocalBroker::open(&path).unwrap();
+    broker.mq_publish("audit", b"kept").await.unwrap();
+    drop(broker);
+    let intact = std::fs::read(&path).unwrap();
+    let mut garbled = intact[8..].to_vec();
+    *garbled.last_mut().unwrap() ^= 0xff;
+
+    // A torn header, a torn body, and a complete record with a bad
+    // checksum, as a short or garbled write at the end would leave.
+    for garbage in [&garbled[..5], &garbled[..garbled.len() - 1], &garbled] {
+        let mut log = OpenOptions::new().append(true).open(&path).unwrap();
+        log.write_all(garbage).unwrap();
+        drop(log);
+
+        let broker = LocalBroker::open(&path).unwrap();
+        assert_eq!(broker.buffered("audit"), 1);
+        drop(broker);
+        assert_eq!(std::fs::read(&path).unwrap(), intact);
+    }
+
+    // The same damage followed by more records is corruption.
+    let mut log = OpenOptions::new().append(true).open(&path).unwrap();
+    log.write_all(&garbled).unwrap();
+    log.write_all(&intact[8..]).unwrap();
+    drop(log);
+    let err = LocalBroker::open(&path).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[tokio::test]
+async fn garbled_length_is_corruption() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broker.log");
+
+    let broker = LocalBroker::open(&path).unwrap();
+    for payload in ["a", "b", "c"] {
+        broker
+            .mq_publish("audit", payload.as_bytes())
+            .await
+            .unwrap();
+    }
+    drop(broker);
+    let mut data = std::fs::read(&path).unwrap();
+
+    // Too long to fit, and too short: either way the records after the
+    // first one must not be dropped silently.
+    for len in [u32::MAX, 1] {
+        data[8..12].copy_from_slice(&len.to_le_bytes());
+        std::fs::write(&path, &data).unwrap();
+        let err = LocalBroker::open(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput, "{len}");
+    }
+}
+
+#[tokio::test]
+async fn rejects_foreign_log() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broker.log");
+    std::fs::write(&path, "not a broker log").unwrap();
+    let err = LocalBroker::open(&path).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
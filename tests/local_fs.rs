@@ -0,0 +1,206 @@
+#![cfg(feature = "local-fs")]
+
+use extio::backend::LocalFs;
+use extio::fs::{DirEntry, FileKind, Permissions, WalkOptions, WriteOptions};
+use extio::stream::reader_from_bytes;
+use extio::{ErrorKind, FileIo, HasErrorKind};
+use futures_util::TryStreamExt;
+use tokio::io::AsyncReadExt;
+
+fn names(entries: &[DirEntry]) -> Vec<&str> {
+    entries.iter().map(|e| e.name.as_str()).collect()
+}
+
+#[tokio::test]
+async fn write_read_list_delete() {
+    let dir = tempfile::tempdir().unwrap();
+    let fs = LocalFs::new();
+    let a = dir.path().join("a.txt");
+    let b = dir.path().join("b.bin");
+
+    fs.write_file(&a, b"hello").await.unwrap();
+    fs.write_file(&b, &[0, 1, 2]).await.unwrap();
+    assert_eq!(fs.read_file(&a).await.unwrap(), b"hello");
+
+    fs.write_file(&a, b"hi").await.unwrap();
+    assert_eq!(fs.read_file(&a).await.unwrap(), b"hi");
+
+    let entries = fs.list_dir(dir.path()).await.unwrap();
+    assert_eq!(names(&entries), ["a.txt", "b.bin"]);
+    assert!(entries.iter().all(|e| e.kind == FileKind::File));
+    assert_eq!(entries[0].size, 2);
+    assert_eq!(entries[0].path, a);
+    assert!(entries[0].modified.is_some());
+
+    fs.delete_file(&a).await.unwrap();
+    assert_eq!(names(&fs.list_dir(dir.path()).await.unwrap()), ["b.bin"]);
+}
+
+#[tokio::test]
+async fn missing_file_is_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+    let fs = LocalFs::new();
+    let missing = dir.path().join("missing");
+
+    assert_eq!(
+        fs.read_file(&missing).await.unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        fs.delete_file(&missing).await.unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        fs.list_dir(&missing).await.unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+}
+
+#[tokio::test]
+async fn stream_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let fs = LocalFs::new();
+    let path = dir.path().join("big.bin");
+    let data: Vec<u8> = (0..1_000_000u32).map(|i| i as u8).collect();
+
+    let written = fs
+        .write_file_stream(&path, reader_from_bytes(data.clone()))
+        .await
+        .unwrap();
+    assert_eq!(written, data.len() as u64);
+
+    let mut reader = fs.read_file_stream(&path).await.unwrap();
+    let mut read = Vec::new();
+    reader.read_to_end(&mut read).await.unwrap();
+    assert_eq!(read, data);
+}
+
+#[tokio::test]
+async fn walk_dir_depth_and_glob() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src/nested")).unwrap();
+    std::fs::write(root.join("README.md"), "").unwrap();
+    std::fs::write(root.join("src/lib.rs"), "").unwrap();
+    std::fs::write(root.join("src/nested/mod.rs"), "").unwrap();
+    let fs = LocalFs::new();
+
+    let all: Vec<_> = fs
+        .walk_dir(root, WalkOptions::new())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        names(&all),
+        ["README.md", "src", "lib.rs", "nested", "mod.rs"]
+    );
+
+    let shallow: Vec<_> = fs
+        .walk_dir(root, WalkOptions::new().max_depth(2))
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(names(&shallow), ["README.md", "src", "lib.rs", "nested"]);
+
+    let rust: Vec<_> = fs
+        .walk_dir(root, WalkOptions::new().glob("**/*.rs").unwrap())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(names(&rust), ["lib.rs", "mod.rs"]);
+
+    let top: Vec<_> = fs
+        .walk_dir(root, WalkOptions::new().glob("*/*.rs").unwrap())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(names(&top), ["lib.rs"]);
+}
+
+#[tokio::test]
+async fn metadata_and_directory_management() {
+    let dir = tempfile::tempdir().unwrap();
+    let fs = LocalFs::new();
+    let nested = dir.path().join("a/b/c");
+    let log = nested.join("log.txt");
+
+    fs.create_dir_all(&nested).await.unwrap();
+    fs.create_dir_all(&nested).await.unwrap();
+    assert_eq!(fs.stat(&nested).await.unwrap().kind, FileKind::Dir);
+
+    fs.append_file(&log, b"one\n").await.unwrap();
+    fs.append_file(&log, b"two\n").await.unwrap();
+    assert_eq!(fs.read_file(&log).await.unwrap(), b"one\ntwo\n");
+    assert_eq!(fs.stat(&log).await.unwrap().size, 8);
+
+    let copy = dir.path().join("copy.txt");
+    assert_eq!(fs.copy(&log, &copy).await.unwrap(), 8);
+    let moved = dir.path().join("moved.txt");
+    fs.rename(&copy, &moved).await.unwrap();
+    assert!(!fs.exists(&copy).await.unwrap());
+    assert!(fs.exists(&moved).await.unwrap());
+
+    let readonly = Permissions {
+        readonly: true,
+        mode: None,
+    };
+    fs.set_permissions(&moved, readonly).await.unwrap();
+    assert!(fs.stat(&moved).await.unwrap().permissions.readonly);
+    let writable = Permissions {
+        readonly: false,
+        mode: None,
+    };
+    fs.set_permissions(&moved, writable).await.unwrap();
+    let perms = fs.stat(&moved).await.unwrap().permissions;
+    assert!(!perms.readonly);
+    #[cfg(unix)]
+    assert_eq!(perms.mode.unwrap() & 0o222, 0o200);
+    fs.append_file(&moved, b"three\n").await.unwrap();
+
+    fs.remove_dir_all(&dir.path().join("a")).await.unwrap();
+    assert!(!fs.exists(&log).await.unwrap());
+    assert_eq!(fs.stat(&log).await.unwrap_err().kind(), ErrorKind::NotFound);
+}
+
+#[tokio::test]
+async fn atomic_and_create_new_writes() {
+    let dir = tempfile::tempdir().unwrap();
+    let fs = LocalFs::new();
+    let path = dir.path().join("state.json");
+
+    fs.atomic_write_file(&path, b"{}").await.unwrap();
+    fs.atomic_write_file(&path, b"{\"v\":1}").await.unwrap();
+    assert_eq!(fs.read_file(&path).await.unwrap(), b"{\"v\":1}");
+
+    let err = fs
+        .write_file_with(
+            &path,
+            b"x",
+            WriteOptions::new().atomic(true).create_new(true),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    let err = fs
+        .write_file_with(&path, b"x", WriteOptions::new().create_new(true))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(fs.read_file(&path).await.unwrap(), b"{\"v\":1}");
+
+    let fresh = dir.path().join("fresh");
+    fs.write_file_with(
+        &fresh,
+        b"new",
+        WriteOptions::new().atomic(true).create_new(true),
+    )
+    .await
+    .unwrap();
+    assert_eq!(fs.read_file(&fresh).await.unwrap(), b"new");
+
+    // No temporary siblings are left behind.
+    assert_eq!(
+        names(&fs.list_dir(dir.path()).await.unwrap()),
+        ["fresh", "state.json"]
+    );
+}
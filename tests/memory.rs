@@ -0,0 +1,157 @@
+use std::path::Path;
+use std::time::Duration;
+
+use extio::backend::{InMemoryExtio, LogRecord};
+use extio::fs::{FileKind, Permissions};
+use extio::mq::Message;
+use extio::{
+    Backend, Capability, Database, Env, ErrorKind, FileIo, HasErrorKind, Ipc, Logger, MessageQueue,
+    Metrics, ObjectStore,
+};
+
+#[tokio::test]
+async fn virtual_file_tree() {
+    let mem = InMemoryExtio::new().with_file("/etc/app.toml", "debug = true");
+    let dir = Path::new("/var/lib/app");
+
+    assert_eq!(
+        mem.write_file(&dir.join("state"), b"x")
+            .await
+            .unwrap_err()
+            .kind(),
+        ErrorKind::NotFound
+    );
+    mem.create_dir_all(dir).await.unwrap();
+    mem.write_file(&dir.join("state"), b"v1").await.unwrap();
+    mem.append_file(&dir.join("state"), b"+v2").await.unwrap();
+    mem.rename(Path::new("/var/lib/app"), Path::new("/var/lib/svc"))
+        .await
+        .unwrap();
+
+    let entries = mem.list_dir(Path::new("/var/lib")).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "svc");
+    assert_eq!(entries[0].kind, FileKind::Dir);
+
+    assert_eq!(mem.file("/var/lib/svc/state").unwrap(), b"v1+v2");
+    assert_eq!(mem.file("/etc/app.toml").unwrap(), b"debug = true");
+    assert_eq!(mem.files().len(), 2);
+
+    let (config, moved) = (Path::new("/etc/app.toml"), Path::new("/etc/old.toml"));
+    let readonly = Permissions {
+        readonly: true,
+        mode: None,
+    };
+    mem.set_permissions(config, readonly).await.unwrap();
+    for (from, to) in [(config, moved), (Path::new("/var/lib/svc/state"), config)] {
+        let err = mem.rename(from, to).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+    assert_eq!(mem.file("/etc/app.toml").unwrap(), b"debug = true");
+
+    mem.remove_dir_all(Path::new("/var")).await.unwrap();
+    assert!(!mem.exists(Path::new("/var/lib/svc/state")).await.unwrap());
+}
+
+#[tokio::test]
+async fn storage_mq_ipc_env_logs() {
+    let mem = InMemoryExtio::new().with_env("MODE", "test");
+
+    mem.storage_put("a/b", b"blob".to_vec()).await.unwrap();
+    assert_eq!(mem.storage_get("a/b").await.unwrap(), b"blob");
+    assert_eq!(mem.object("a/b").unwrap(), b"blob");
+    mem.storage_delete("a/b").await.unwrap();
+    assert_eq!(
+        mem.storage_get("a/b").await.unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+
+    let consumer = mem.mq_consume("jobs");
+    mem.mq_publish("jobs", b"first").await.unwrap();
+    mem.mq_publish("jobs", b"second").await.unwrap();
+    let delivery = consumer.await.unwrap();
+    assert_eq!(delivery.payload(), b"first");
+    delivery.ack().await.unwrap();
+    assert_eq!(
+        mem.published("jobs"),
+        [b"first".to_vec(), b"second".to_vec()]
+    );
+
+    mem.ipc_send("ctl", b"stop").await.unwrap();
+    assert_eq!(mem.ipc_receive("ctl").await.unwrap(), b"stop");
+    assert_eq!(mem.ipc_sent("ctl"), [b"stop".to_vec()]);
+    let pending = tokio::time::timeout(Duration::from_millis(10), mem.ipc_receive("ctl"));
+    assert!(pending.await.is_err());
+
+    assert_eq!(mem.get_env("MODE").as_deref(), Some("test"));
+    mem.set_env("MODE", "prod").unwrap();
+    assert_eq!(mem.env_vars()["MODE"], "prod");
+
+    mem.log("info", "started");
+    mem.record_metric("requests", 1.0);
+    assert_eq!(
+        mem.logs(),
+        [LogRecord {
+            level: "info".to_owned(),
+            message: "started".to_owned(),
+        }]
+    );
+    assert_eq!(mem.metrics(), [("requests".to_owned(), 1.0)]);
+}
+
+#[tokio::test]
+async fn unsupported_capabilities() {
+    let mem = InMemoryExtio::new();
+
+    assert!(mem.capabilities().contains(Capability::Files));
+    assert!(!mem.capabilities().contains(Capability::Db));
+    assert_eq!(
+        mem.db_execute("DELETE FROM t", &[])
+            .await
+            .unwrap_err()
+            .kind(),
+        ErrorKind::Unsupported
+    );
+}
+
+#[tokio::test]
+async fn mq_acks_and_consumer_groups() {
+    let mem = InMemoryExtio::new();
+    let id = mem
+        .mq_publish_with("orders", Message::new("o-1").header("tenant", "acme"))
+        .await
+        .unwrap();
+    mem.mq_publish("orders", b"o-2").await.unwrap();
+
+    // Workers in one group share the messages.
+    let first = mem.mq_consume_group("orders", "billing").await.unwrap();
+    let second = mem.mq_consume_group("orders", "billing").await.unwrap();
+    assert_eq!(first.id(), id);
+    assert_eq!(first.header("tenant"), Some("acme"));
+    assert_eq!(first.redelivery_count(), 0);
+    assert_eq!(second.payload(), b"o-2");
+
+    // Requeued and dropped deliveries come back with a higher count.
+    first.requeue().await.unwrap();
+    drop(second);
+    let again = mem.mq_consume_group("orders", "billing").await.unwrap();
+    assert_eq!(
+        (again.payload(), again.redelivery_count()),
+        (&b"o-1"[..], 1)
+    );
+    again.ack().await.unwrap();
+    let again = mem.mq_consume_group("orders", "billing").await.unwrap();
+    assert_eq!(again.payload(), b"o-2");
+    again.nack().await.unwrap();
+    let idle = tokio::time::timeout(
+        Duration::from_millis(10),
+        mem.mq_consume_group("orders", "billing"),
+    );
+    assert!(idle.await.is_err());
+
+    // Another group sees every message from the start.
+    let shipping = mem.mq_consume_group("orders", "shipping").await.unwrap();
+    assert_eq!(shipping.payload(), b"o-1");
+    assert!(shipping.timestamp() <= std::time::SystemTime::now());
+    shipping.ack().await.unwrap();
+}
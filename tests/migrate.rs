@@ -0,0 +1,157 @@
+#![cfg(feature = "sqlite")]
+
+use std::path::Path;
+
+use extio::backend::{InMemoryExtio, SqliteDb};
+use extio::db::{Migration, Migrator};
+use extio::{Database, ErrorKind, HasErrorKind};
+
+fn source() -> InMemoryExtio {
+    InMemoryExtio::new()
+        .with_file(
+            "/migrations/0001_accounts.up.sql",
+            "CREATE TABLE accounts (id INTEGER PRIMARY KEY, owner TEXT NOT NULL);",
+        )
+        .with_file("/migrations/0001_accounts.down.sql", "DROP TABLE accounts;")
+        .with_file(
+            "/migrations/0002_balance.up.sql",
+            "ALTER TABLE accounts ADD COLUMN balance REAL NOT NULL DEFAULT 0;
+             CREATE INDEX accounts_owner ON accounts (owner);",
+        )
+        .with_file(
+            "/migrations/0002_balance.down.sql",
+            "DROP INDEX accounts_owner; ALTER TABLE accounts DROP COLUMN balance;",
+        )
+        .with_file(
+            "/migrations/0003_seed.sql",
+            "INSERT INTO accounts (owner) VALUES ('bank');",
+        )
+        .with_file("/migrations/README.md", "ignored")
+}
+
+#[tokio::test]
+async fn up_and_down_from_dir() {
+    let db = SqliteDb::memory().unwrap();
+    let migrator = Migrator::from_dir(&source(), Path::new("/migrations"))
+        .await
+        .unwrap();
+    assert_eq!(migrator.migrations().len(), 3);
+
+    assert_eq!(migrator.up_to(&db, 2).await.unwrap(), [1, 2]);
+    assert_eq!(migrator.pending(&db).await.unwrap().len(), 1);
+    assert_eq!(migrator.up(&db).await.unwrap(), [3]);
+    assert!(migrator.up(&db).await.unwrap().is_empty());
+
+    let applied = migrator.applied(&db).await.unwrap();
+    assert_eq!(applied[1].name, "balance");
+    assert_eq!(applied[1].checksum, migrator.migrations()[1].checksum());
+    let balances = db
+        .db_query_as::<f64>("SELECT balance FROM accounts", &[])
+        .await
+        .unwrap();
+    assert_eq!(balances, [0.0]);
+
+    // 0003 has no down script.
+    let err = migrator.down(&db, 1).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(migrator.applied(&db).await.unwrap().len(), 3);
+
+    db.db_execute("DELETE FROM _extio_migrations WHERE version = 3", &[])
+        .await
+        .unwrap();
+    assert_eq!(migrator.down(&db, 1).await.unwrap(), [2]);
+    assert_eq!(migrator.down_to(&db, None).await.unwrap(), [1]);
+    let tables = db
+        .db_query_as::<String>(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'accounts'",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(tables.is_empty());
+}
+
+#[tokio::test]
+async fn detects_edited_migrations() {
+    let db = SqliteDb::memory().unwrap();
+    let v1 = Migration::new(1, "t", "CREATE TABLE t (a INTEGER);");
+    Migrator::new([v1])
+        .unwrap()
+        .table("schema_versions")
+        .up(&db)
+        .await
+        .unwrap();
+
+    let edited = Migration::new(1, "t", "CREATE TABLE t (a INTEGER, b TEXT);");
+    let err = Migrator::new([edited])
+        .unwrap()
+        .table("schema_versions")
+        .up(&db)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("modified"), "{err}");
+}
+
+#[tokio::test]
+async fn failed_migration_is_not_recorded() {
+    let db = SqliteDb::memory().unwrap();
+    let migrator = Migrator::from_files([
+        ("1_ok.sql", "CREATE TABLE ok (a INTEGER);"),
+        (
+            "2_broken.sql",
+            "CREATE TABLE half (a INTEGER); CREATE TABLE ok (a INTEGER);",
+        ),
+    ])
+    .unwrap();
+
+    assert!(migrator.up(&db).await.is_err());
+    let applied = migrator.applied(&db).await.unwrap();
+    assert_eq!(applied.len(), 1);
+    let half = db
+        .db_query_as::<String>("SELECT name FROM sqlite_master WHERE name = 'half'", &[])
+        .await
+        .unwrap();
+    assert!(half.is_empty());
+
+    let err = Migrator::from_files([("init.sql", "")]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[tokio::test]
+async fn version_zero_and_mismatched_names() {
+    let db = SqliteDb::memory().unwrap();
+    let migrator = Migrator::new([
+        Migration::new(0, "base", "CREATE TABLE base (a INTEGER);").with_down("DROP TABLE base;"),
+        Migration::new(1, "more", "CREATE TABLE more (a INTEGER);").with_down("DROP TABLE more;"),
+    ])
+    .unwrap();
+    assert_eq!(migrator.up(&db).await.unwrap(), [0, 1]);
+    assert_eq!(migrator.down_to(&db, Some(0)).await.unwrap(), [1]);
+    assert_eq!(migrator.down(&db, 1).await.unwrap(), [0]);
+    assert!(migrator.applied(&db).await.unwrap().is_empty());
+    migrator.up(&db).await.unwrap();
+    assert_eq!(migrator.down(&db, usize::MAX).await.unwrap(), [1, 0]);
+
+    let err = Migrator::from_files([
+        (
+            "0001_accounts.up.sql",
+            "CREATE TABLE accounts (id INTEGER);",
+        ),
+        ("0001_acounts.down.sql", "DROP TABLE accounts;"),
+    ])
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn rejects_duplicate_versions() {
+    let err = Migrator::new([
+        Migration::new(2, "users", "CREATE TABLE users (id INTEGER);"),
+        Migration::new(1, "init", ""),
+        Migration::new(2, "accounts", "CREATE TABLE accounts (id INTEGER);"),
+    ])
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("migration 2"), "{err}");
+}
@@ -0,0 +1,168 @@
+#![cfg(unix)]
+
+use std::time::{Duration, Instant};
+
+use extio::backend::TokioProcess;
+use extio::process::{ExecOptions, ExitStatus, ResourceLimits};
+use extio::{ErrorKind, Exec, HasErrorKind};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+#[tokio::test]
+async fn separate_streams_and_status() {
+    let exec = TokioProcess::new();
+    let out = exec
+        .exec("sh", &["-c", "echo out; echo err >&2; exit 3"])
+        .await
+        .unwrap();
+    assert_eq!(out.status, ExitStatus::Code(3));
+    assert!(!out.success());
+    assert_eq!(out.stdout, b"out\n");
+    assert_eq!(out.stderr, b"err\n");
+
+    let out = exec.exec("sh", &["-c", "kill -TERM $$"]).await.unwrap();
+    assert_eq!(out.status, ExitStatus::Signal(15));
+    assert_eq!(out.status.code(), None);
+
+    let err = exec.exec("extio-no-such-command", &[]).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[tokio::test]
+async fn stdin_cwd_and_env() {
+    let exec = TokioProcess::new();
+    let dir = tempfile::tempdir().unwrap();
+    let options = ExecOptions::new()
+        .stdin("b\na\n")
+        .cwd(dir.path())
+        .env_clear(true)
+        .env("PATH", std::env::var("PATH").unwrap())
+        .env("GREETING", "hi")
+        .env("GONE", "x")
+        .env_remove("GONE");
+
+    let out = exec.exec_with("sort", &[], &options).await.unwrap();
+    assert_eq!(out.stdout, b"a\nb\n");
+
+    let script = "pwd; echo $GREETING; echo ${GONE:-unset}; echo ${HOME:-unset}";
+    let out = exec
+        .exec_with("sh", &["-c", script], &options)
+        .await
+        .unwrap();
+    let expected = format!(
+        "{}\nhi\nunset\nunset\n",
+        dir.path().canonicalize().unwrap().display()
+    );
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), expected);
+
+    // A child that ignores its input must not fail the call.
+    let options = ExecOptions::new().stdin(vec![b'x'; 1 << 20]);
+    assert!(
+        exec.exec_with("true", &[], &options)
+            .await
+            .unwrap()
+            .success()
+    );
+}
+
+#[tokio::test]
+async fn timeout_kills_the_child() {
+    let exec = TokioProcess::new();
+    let started = Instant::now();
+    let options = ExecOptions::new().timeout(Duration::from_millis(200));
+    let err = exec
+        .exec_with("sleep", &["10"], &options)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Timeout);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn spawn_streams_lines() {
+    let exec = TokioProcess::new();
+    let script = "for i in 1 2 3; do echo line $i; sleep 0.05; done; echo done >&2";
+    let mut child = exec
+        .spawn("sh", &["-c", script], &ExecOptions::new())
+        .await
+        .unwrap();
+    assert!(child.id().is_some());
+
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    let mut seen = Vec::new();
+    while let Some(line) = lines.next_line().await.unwrap() {
+        seen.push(line);
+    }
+    assert_eq!(seen, ["line 1", "line 2", "line 3"]);
+
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .await
+        .unwrap();
+    assert_eq!(stderr, "done\n");
+    assert_eq!(child.wait().await.unwrap(), ExitStatus::Code(0));
+}
+
+#[tokio::test]
+async fn spawn_interactive_stdin() {
+    let exec = TokioProcess::new();
+    let mut child = exec.spawn("cat", &[], &ExecOptions::new()).await.unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+
+    stdin.write_all(b"ping\n").await.unwrap();
+    stdin.flush().await.unwrap();
+    assert_eq!(stdout.next_line().await.unwrap().as_deref(), Some("ping"));
+    stdin.write_all(b"pong\n").await.unwrap();
+    drop(stdin);
+    assert_eq!(stdout.next_line().await.unwrap().as_deref(), Some("pong"));
+    assert_eq!(stdout.next_line().await.unwrap(), None);
+    assert!(child.wait().await.unwrap().success());
+
+    let options = ExecOptions::new().stdin("fed\n");
+    let mut child = exec.spawn("cat", &[], &options).await.unwrap();
+    assert!(child.stdin.is_none());
+    let mut out = String::new();
+    child
+        .stdout
+        .take()
+        .unwrap()
+        .read_to_string(&mut out)
+        .await
+        .unwrap();
+    assert_eq!(out, "fed\n");
+}
+
+#[tokio::test]
+async fn spawn_kill_and_timeout() {
+    let exec = TokioProcess::new();
+    let mut child = exec
+        .spawn("sleep", &["30"], &ExecOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(child.try_wait().unwrap(), None);
+    child.kill().await.unwrap();
+    assert_eq!(child.wait().await.unwrap(), ExitStatus::Signal(9));
+
+    let options = ExecOptions::new().timeout(Duration::from_millis(100));
+    let mut child = exec.spawn("sleep", &["30"], &options).await.unwrap();
+    let err = child.wait().await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Timeout);
+    assert!(child.try_wait().unwrap().is_some());
+}
+
+#[tokio::test]
+async fn cpu_time_under_a_second_is_rounded_up() {
+    for limit in [Duration::ZERO, Duration::from_millis(1)] {
+        let options = ExecOptions::new().limits(ResourceLimits::new().cpu_time(limit));
+        let out = TokioProcess::new()
+            .exec_with("sh", &["-c", "echo ok"], &options)
+            .await
+            .unwrap();
+        assert_eq!(out.status, ExitStatus::Code(0), "{limit:?}");
+        assert_eq!(out.stdout, b"ok\n");
+    }
+}
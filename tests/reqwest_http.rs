@@ -0,0 +1,110 @@
+#![cfg(feature = "reqwest")]
+
+use std::time::Duration;
+
+use extio::backend::ReqwestHttp;
+use extio::net::Body;
+use extio::{ErrorKind, HasErrorKind, HttpClient};
+use futures_util::{StreamExt, stream};
+use http::Request;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+/// Serves one canned response per connection and returns the address.
+async fn serve_once(response: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0; 4096];
+        let _ = stream.read(&mut buf).await.unwrap();
+        stream.write_all(response.as_bytes()).await.unwrap();
+    });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn request_round_trip() {
+    let url = serve_once(
+        "HTTP/1.1 418 I'm a teapot\r\nx-test: yes\r\ncontent-length: 5\r\nconnection: close\r\n\r\nshort",
+    )
+    .await;
+    let http = ReqwestHttp::builder()
+        .timeout(Duration::from_secs(5))
+        .no_proxy()
+        .build()
+        .unwrap();
+
+    let req = Request::post(url).body(b"ping".to_vec()).unwrap();
+    let resp = http.http_request(req).await.unwrap();
+
+    assert_eq!(resp.status(), 418);
+    assert_eq!(resp.headers()["x-test"], "yes");
+    assert_eq!(resp.body(), b"short");
+}
+
+#[tokio::test]
+async fn connection_refused_is_categorized() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    let http = ReqwestHttp::builder().no_proxy().build().unwrap();
+
+    let req = Request::get(format!("http://{addr}"))
+        .body(Vec::new())
+        .unwrap();
+    let err = http.http_request(req).await.unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::ConnectionRefused);
+}
+
+#[test]
+fn invalid_proxy_is_rejected() {
+    let err = ReqwestHttp::builder()
+        .proxy("not a url")
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[tokio::test]
+async fn streams_both_directions() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (resume_tx, resume_rx) = oneshot::channel::<()>();
+    let server = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 4096];
+        while !request.ends_with(b"0\r\n\r\n") {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n5\r\nfirst\r\n")
+            .await
+            .unwrap();
+        // The second chunk is only sent once the client has seen the first.
+        resume_rx.await.unwrap();
+        stream.write_all(b"6\r\nsecond\r\n0\r\n\r\n").await.unwrap();
+        String::from_utf8(request).unwrap()
+    });
+
+    let http = ReqwestHttp::builder().no_proxy().build().unwrap();
+    let upload = stream::iter(["up-", "load"].map(Ok::<_, std::io::Error>));
+    let req = Request::put(format!("http://{addr}"))
+        .body(Body::from_stream(upload))
+        .unwrap();
+    let mut body = http.http_request_stream(req).await.unwrap().into_body();
+
+    assert!(body.is_stream());
+    assert_eq!(body.next().await.unwrap().unwrap(), "first");
+    resume_tx.send(()).unwrap();
+    assert_eq!(body.collect().await.unwrap(), "second");
+
+    let request = server.await.unwrap();
+    assert!(request.contains("transfer-encoding: chunked"));
+    assert!(request.contains("up-"));
+    assert!(request.contains("load"));
+}
@@ -0,0 +1,178 @@
+#![cfg(unix)]
+
+use std::path::Path;
+use std::time::Duration;
+
+use extio::backend::TokioProcess;
+use extio::process::{CommandRule, ExecOptions, ExecPolicy, ExitStatus, ResourceLimits, Sandboxed};
+use extio::{Backend, Capability, ErrorKind, Exec, HasErrorKind};
+
+fn sandbox() -> Sandboxed<TokioProcess> {
+    let policy = ExecPolicy::new()
+        .allow(
+            CommandRule::new("echo")
+                .arg("hello")
+                .unwrap()
+                .arg("--name=*")
+                .unwrap()
+                .max_args(2),
+        )
+        .allow(CommandRule::new("env"))
+        .allow(CommandRule::new("sh").any_args())
+        .allow(CommandRule::new("dd").any_args())
+        .inherit_env("PATH")
+        .allow_env_override("EXTIO_SANDBOX_TOKEN");
+    Sandboxed::new(TokioProcess::new(), policy)
+}
+
+#[tokio::test]
+async fn allowlist_and_argument_patterns() {
+    let exec = sandbox();
+    assert!(exec.capabilities().contains(Capability::Exec));
+
+    let out = exec.exec("echo", &["hello", "--name=bob"]).await.unwrap();
+    assert_eq!(out.stdout, b"hello --name=bob\n");
+
+    for (cmd, args) in [
+        ("rm", &["-rf", "/tmp/x"][..]),
+        ("echo", &["goodbye"]),
+        ("echo", &["hello", "hello", "hello"]),
+        ("env", &["rm"]),
+    ] {
+        let err = exec.exec(cmd, args).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Denied, "{cmd} {args:?}");
+    }
+    let err = exec
+        .spawn("rm", &[], &ExecOptions::new())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Denied);
+}
+
+#[tokio::test]
+async fn environment_is_scrubbed() {
+    let exec = sandbox();
+    let out = exec.exec("env", &[]).await.unwrap();
+    let vars: Vec<_> = String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.split('=').next().unwrap().to_owned())
+        .collect();
+    assert_eq!(vars, ["PATH"]);
+
+    let options = ExecOptions::new().env("EXTIO_SANDBOX_TOKEN", "t");
+    let out = exec.exec_with("env", &[], &options).await.unwrap();
+    assert!(
+        String::from_utf8(out.stdout)
+            .unwrap()
+            .contains("EXTIO_SANDBOX_TOKEN=t")
+    );
+
+    for key in ["LD_PRELOAD", "PATH"] {
+        let options = ExecOptions::new().env(key, "/tmp/evil");
+        let err = exec.exec_with("env", &[], &options).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Denied, "{key}");
+    }
+}
+
+#[tokio::test]
+async fn path_cannot_redirect_commands() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let fake = dir.path().join("echo");
+    std::fs::write(&fake, "#!/bin/sh\necho evil\n").unwrap();
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    // Even with PATH explicitly overridable, `echo` is resolved against the
+    // parent's PATH before the child sees the caller's.
+    let exec = Sandboxed::new(
+        TokioProcess::new(),
+        ExecPolicy::new()
+            .allow(CommandRule::new("echo").any_args())
+            .allow(CommandRule::new("./echo").any_args())
+            .allow_env_override("PATH")
+            .allow_cwd(dir.path()),
+    );
+    let options = ExecOptions::new()
+        .env("PATH", dir.path().to_str().unwrap())
+        .cwd(dir.path());
+    let out = exec.exec_with("echo", &["hi"], &options).await.unwrap();
+    assert_eq!(out.stdout, b"hi\n");
+
+    let err = exec.exec_with("./echo", &[], &options).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Denied);
+    for cwd in [Path::new("/"), &dir.path().join("..")] {
+        let options = ExecOptions::new().cwd(cwd);
+        let err = exec.exec_with("echo", &[], &options).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Denied, "{cwd:?}");
+    }
+}
+
+#[tokio::test]
+async fn resource_limits() {
+    let dir = tempfile::tempdir().unwrap();
+    let exec = Sandboxed::new(
+        TokioProcess::new(),
+        ExecPolicy::new()
+            .allow(CommandRule::new("dd").any_args())
+            .allow(CommandRule::new("sh").any_args())
+            .inherit_env("PATH")
+            .limits(
+                ResourceLimits::new()
+                    .file_size(1000)
+                    .cpu_time(Duration::from_secs(1)),
+            )
+            .timeout(Duration::from_secs(10)),
+    );
+
+    let options = ExecOptions::new().cwd(dir.path());
+    let out = exec
+        .exec_with(
+            "dd",
+            &["if=/dev/zero", "of=out", "bs=1000", "count=10"],
+            &options,
+        )
+        .await
+        .unwrap();
+    // SIGXFSZ
+    assert_eq!(out.status, ExitStatus::Signal(25));
+
+    let out = exec
+        .exec("sh", &["-c", "while :; do :; done"])
+        .await
+        .unwrap();
+    assert!(out.status.signal().is_some(), "{:?}", out.status);
+
+    let options = ExecOptions::new().timeout(Duration::from_millis(100));
+    let err = exec
+        .exec_with("sh", &["-c", "sleep 5"], &options)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Timeout);
+}
+
+#[tokio::test]
+async fn timeout_holds_without_wait() {
+    let exec = Sandboxed::new(
+        TokioProcess::new(),
+        ExecPolicy::new()
+            .allow(CommandRule::new("sleep").any_args())
+            .timeout(Duration::from_millis(100)),
+    );
+    let mut child = exec
+        .spawn("sleep", &["30"], &ExecOptions::new())
+        .await
+        .unwrap();
+    let pid = child.id().unwrap().to_string();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(child.try_wait().unwrap(), Some(ExitStatus::Signal(9)));
+    // Reaped too, not just signalled.
+    let alive = std::process::Command::new("kill")
+        .args(["-0", &pid])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .unwrap();
+    assert!(!alive.success());
+}
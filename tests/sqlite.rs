@@ -0,0 +1,188 @@
+#![cfg(feature = "sqlite")]
+
+use std::time::{Duration, SystemTime};
+
+use extio::backend::SqliteDb;
+use extio::db::DbValue;
+use extio::{Database, ErrorKind, HasErrorKind};
+use serde::Deserialize;
+
+const SCHEMA: &str = "
+    CREATE TABLE ledger (
+        id INTEGER PRIMARY KEY,
+        account TEXT NOT NULL UNIQUE,
+        amount REAL NOT NULL,
+        settled BOOLEAN NOT NULL,
+        booked_at TIMESTAMP,
+        memo BLOB
+    );
+";
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Entry {
+    account: String,
+    amount: f64,
+    settled: bool,
+    booked_at: Option<SystemTime>,
+}
+
+async fn ledger() -> SqliteDb {
+    let db = SqliteDb::memory().unwrap();
+    db.db_execute(SCHEMA, &[]).await.unwrap();
+    db
+}
+
+#[tokio::test]
+async fn typed_round_trip() {
+    let db = ledger().await;
+    let booked = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 250_000_000);
+    let insert = db
+        .db_prepare("INSERT INTO ledger (account, amount, settled, booked_at, memo) VALUES (?1, ?2, ?3, ?4, ?5)")
+        .await
+        .unwrap();
+    assert_eq!(insert.params(), Some(5));
+
+    let n = db
+        .db_execute(
+            &insert,
+            &[
+                "alice".into(),
+                12.5.into(),
+                true.into(),
+                booked.into(),
+                b"x".as_slice().into(),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(n, 1);
+    db.db_execute(
+        &insert,
+        &[
+            "bob".into(),
+            (-3).into(),
+            false.into(),
+            DbValue::Null,
+            DbValue::Null,
+        ],
+    )
+    .await
+    .unwrap();
+
+    let rows = db
+        .db_query(
+            "SELECT account, settled, booked_at, memo FROM ledger ORDER BY id",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.columns(), ["account", "settled", "booked_at", "memo"]);
+    let first = &rows.rows()[0];
+    assert_eq!(first.value("settled"), Some(&DbValue::Bool(true)));
+    assert_eq!(first.get::<SystemTime>("booked_at").unwrap(), booked);
+    assert_eq!(first.get::<Vec<u8>>("memo").unwrap(), b"x");
+    assert_eq!(rows.rows()[1].value("booked_at"), Some(&DbValue::Null));
+
+    let entries: Vec<Entry> = db
+        .db_query_as(
+            "SELECT account, amount, settled, booked_at FROM ledger WHERE amount > ?1",
+            &[0.into()],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        entries,
+        [Entry {
+            account: "alice".into(),
+            amount: 12.5,
+            settled: true,
+            booked_at: Some(booked),
+        }]
+    );
+
+    let iso = db
+        .db_query_as::<String>(
+            "SELECT strftime('%Y-%m-%d', booked_at) FROM ledger WHERE id = 1",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(iso, ["2023-11-14"]);
+}
+
+#[tokio::test]
+async fn transactions_and_savepoints() {
+    let db = ledger().await;
+    let insert = "INSERT INTO ledger (account, amount, settled) VALUES (?1, ?2, 0)";
+    let count = "SELECT COUNT(*) FROM ledger";
+
+    let mut tx = db.db_begin().await.unwrap();
+    tx.execute(insert, &["alice".into(), 1.into()])
+        .await
+        .unwrap();
+    tx.savepoint("fee").await.unwrap();
+    tx.execute(insert, &["bank".into(), 2.into()])
+        .await
+        .unwrap();
+    tx.rollback_to("fee").await.unwrap();
+    tx.release("fee").await.unwrap();
+    assert_eq!(
+        tx.query(count, &[]).await.unwrap().rows()[0]
+            .get_at::<i64>(0)
+            .unwrap(),
+        1
+    );
+    tx.commit().await.unwrap();
+    assert_eq!(db.db_query_as::<i64>(count, &[]).await.unwrap(), [1]);
+
+    let mut tx = db.db_begin().await.unwrap();
+    tx.execute(insert, &["bob".into(), 3.into()]).await.unwrap();
+    tx.rollback().await.unwrap();
+
+    let mut tx = db.db_begin().await.unwrap();
+    tx.execute(insert, &["carol".into(), 4.into()])
+        .await
+        .unwrap();
+    drop(tx);
+    assert_eq!(db.db_query_as::<i64>(count, &[]).await.unwrap(), [1]);
+}
+
+#[tokio::test]
+async fn errors_map_to_kinds() {
+    let db = ledger().await;
+    let insert = "INSERT INTO ledger (account, amount, settled) VALUES (?1, 0, 0)";
+    db.db_execute(insert, &["alice".into()]).await.unwrap();
+
+    let err = db.db_execute(insert, &["alice".into()]).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Conflict);
+    let err = db.db_prepare("SELEC 1").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let err = db.db_execute(insert, &[]).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[tokio::test]
+async fn file_database_persists() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ledger.db");
+    {
+        let db = SqliteDb::open(&path).unwrap();
+        db.db_execute(SCHEMA, &[]).await.unwrap();
+        db.db_execute(
+            "INSERT INTO ledger (account, amount, settled) VALUES ('alice', 1, 1)",
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+    let db = SqliteDb::builder()
+        .statement_cache_capacity(4)
+        .busy_timeout(Duration::from_secs(1))
+        .open(&path)
+        .unwrap();
+    let accounts = db
+        .db_query_as::<String>("SELECT account FROM ledger", &[])
+        .await
+        .unwrap();
+    assert_eq!(accounts, ["alice"]);
+}
@@ -0,0 +1,89 @@
+use extio::backend::TokioNet;
+use extio::net::{LengthPrefixed, Lines};
+use extio::{ErrorKind, HasErrorKind, Tcp};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn connect_listen_and_frame() {
+    let net = TokioNet::new();
+    let mut listener = net.tcp_listen("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let (conn, peer) = listener.accept().await.unwrap();
+        assert_eq!(conn.peer_addr(), Some(peer));
+        let mut frames = LengthPrefixed::new(conn);
+        while let Some(frame) = frames.recv().await.unwrap() {
+            frames.send(&frame).await.unwrap();
+        }
+
+        let (conn, _) = listener.accept().await.unwrap();
+        let mut lines = Lines::new(conn);
+        while let Some(line) = lines.recv().await.unwrap() {
+            lines.send(&line.to_uppercase()).await.unwrap();
+        }
+    });
+
+    let conn = net.tcp_connect(&addr.to_string()).await.unwrap();
+    assert_eq!(conn.peer_addr(), Some(addr));
+    let mut frames = LengthPrefixed::new(conn);
+    frames.send(b"").await.unwrap();
+    frames.send(b"hello").await.unwrap();
+    assert_eq!(frames.recv().await.unwrap().unwrap(), b"");
+    assert_eq!(frames.recv().await.unwrap().unwrap(), b"hello");
+    frames.into_inner().shutdown().await.unwrap();
+
+    let conn = net.tcp_connect(&addr.to_string()).await.unwrap();
+    let mut lines = Lines::new(conn).max_line_len(8);
+    lines.send("one").await.unwrap();
+    lines.send("two").await.unwrap();
+    assert_eq!(lines.recv().await.unwrap().as_deref(), Some("ONE"));
+    assert_eq!(lines.recv().await.unwrap().as_deref(), Some("TWO"));
+    assert!(lines.send("much too long").await.is_err());
+    assert!(lines.send("a\nb").await.is_err());
+    drop(lines);
+
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn tcp_send_reads_until_eof() {
+    let net = TokioNet::new();
+    let mut listener = net.tcp_listen("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+
+    tokio::spawn(async move {
+        let (mut conn, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        conn.read_to_end(&mut request).await.unwrap();
+        request.reverse();
+        conn.write_all(&request).await.unwrap();
+    });
+
+    assert_eq!(net.tcp_send(&addr, b"abc").await.unwrap(), b"cba");
+}
+
+#[tokio::test]
+async fn connect_refused() {
+    let net = TokioNet::new();
+    let listener = net.tcp_listen("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    drop(listener);
+
+    let err = net.tcp_connect(&addr).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ConnectionRefused);
+}
+
+#[tokio::test]
+async fn lines_skip_oversized_line() {
+    let (near, mut far) = tokio::io::duplex(64);
+    let mut lines = Lines::new(near).max_line_len(4);
+    far.write_all(b"ok\nfar too long\r\nnext\n").await.unwrap();
+    drop(far);
+
+    assert_eq!(lines.recv().await.unwrap().as_deref(), Some("ok"));
+    let err = lines.recv().await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(lines.recv().await.unwrap().as_deref(), Some("next"));
+    assert_eq!(lines.recv().await.unwrap(), None);
+}
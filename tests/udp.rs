@@ -0,0 +1,50 @@
+use std::net::Ipv4Addr;
+
+use extio::backend::TokioNet;
+use extio::{Backend, Capability, Udp};
+
+#[tokio::test]
+async fn send_and_receive_datagrams() {
+    let net = TokioNet::new();
+    assert!(net.capabilities().contains(Capability::Udp));
+
+    let server = net.udp_bind("127.0.0.1:0").await.unwrap();
+    let client = net.udp_bind("127.0.0.1:0").await.unwrap();
+    let server_addr = server.local_addr().unwrap();
+
+    client.send_to(b"ping", server_addr).await.unwrap();
+    let mut buf = [0u8; 16];
+    let (n, peer) = server.recv_from(&mut buf).await.unwrap();
+    assert_eq!(&buf[..n], b"ping");
+    assert_eq!(peer, client.local_addr().unwrap());
+
+    server.send_to(b"pong", peer).await.unwrap();
+    let (n, from) = client.recv_from(&mut buf).await.unwrap();
+    assert_eq!(&buf[..n], b"pong");
+    assert_eq!(from, server_addr);
+
+    net.udp_send(&server_addr.to_string(), b"oneshot")
+        .await
+        .unwrap();
+    let (n, _) = server.recv_from(&mut buf).await.unwrap();
+    assert_eq!(&buf[..n], b"oneshot");
+}
+
+#[tokio::test]
+async fn socket_options() {
+    let net = TokioNet::new();
+    let socket = net.udp_bind("0.0.0.0:0").await.unwrap();
+    socket.set_broadcast(true).unwrap();
+    socket.set_multicast_loop_v4(true).unwrap();
+    socket.set_multicast_ttl_v4(1).unwrap();
+
+    let group = Ipv4Addr::new(239, 255, 42, 99);
+    if socket
+        .join_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+        .is_ok()
+    {
+        socket
+            .leave_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+            .unwrap();
+    }
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "websocket")]
+
+use extio::WebSocket;
+use extio::backend::TungsteniteWs;
+use extio::net::{CloseFrame, WsMessage};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+
+/// Echoes every data frame back until the client closes; returns the URL.
+async fn echo_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                while let Some(Ok(msg)) = ws.next().await {
+                    if msg.is_text() || msg.is_binary() {
+                        ws.send(msg).await.unwrap();
+                    }
+                }
+            });
+        }
+    });
+    format!("ws://{addr}")
+}
+
+#[tokio::test]
+async fn echo_text_binary_ping_and_close() {
+    let url = echo_server().await;
+    let ws = TungsteniteWs::new();
+    let mut a = ws.ws_connect(&url).await.unwrap();
+    let mut b = ws.ws_connect(&url).await.unwrap();
+
+    a.send_text("hello").await.unwrap();
+    b.send_binary(&[1, 2, 3]).await.unwrap();
+    assert_eq!(
+        a.receive().await.unwrap(),
+        Some(WsMessage::Text("hello".to_owned()))
+    );
+    assert_eq!(
+        b.receive().await.unwrap(),
+        Some(WsMessage::Binary(vec![1, 2, 3]))
+    );
+
+    a.ping(b"hb").await.unwrap();
+    assert_eq!(
+        a.receive().await.unwrap(),
+        Some(WsMessage::Pong(b"hb".to_vec()))
+    );
+
+    a.close(Some(CloseFrame::new(CloseFrame::GOING_AWAY, "bye")))
+        .await
+        .unwrap();
+    assert_eq!(
+        a.receive().await.unwrap(),
+        Some(WsMessage::Close(Some(CloseFrame::new(
+            CloseFrame::GOING_AWAY,
+            "bye"
+        ))))
+    );
+    assert_eq!(a.receive().await.unwrap(), None);
+
+    b.send_text("still open").await.unwrap();
+    assert_eq!(
+        b.receive().await.unwrap(),
+        Some(WsMessage::Text("still open".to_owned()))
+    );
+}